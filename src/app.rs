@@ -1,13 +1,38 @@
-use libxivdat::dat_file::DATFile;
-use libxivdat::xiv_gearset::{read_gearset, GearsetFlags, GearsetList};
+use libxivdat::xiv_gearset::{Gearset, GearsetFlags, GearsetList};
 
+use crate::conformance::{check_conformance, SlotStatus};
 use crate::data_provider::DataProvider;
-use crate::exporters::get_xivgear_json;
+use crate::exporters::{
+    get_csv_report, get_gear_usage_report, get_markdown_report, get_share_code, get_teamcraft_json,
+    get_text_report, get_xivgear_json, ExportTarget,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::file_watcher::FileWatcher;
+use crate::gearset_file::{load_gearsets, save_gearsets, truncate_gearset_name};
+use crate::importers::parse_import;
 use crate::iw_provider::IronworksProvider;
+use crate::model::base_param::{base_param_display_name, CRAFTER_PARAMS, GATHERER_PARAMS};
+use crate::model::{
+    class_job_abbreviation, class_job_kind, class_job_level_cap, class_job_name, class_job_role,
+    promote_to_job, ClassJobKind, EquipSlot, ItemId, PortableGearset, PortableItem, Role,
+};
+use crate::snapshots::{
+    character_folder, diff_gearsets, list_snapshots, snapshot_if_changed, GearsetDiff, Snapshot,
+    SnapshotWatcher,
+};
+use crate::stats::gearset_stat_totals;
+use crate::timestamp::display_timestamp;
+use crate::validation::validate_gearset;
+use crate::views::{
+    show_glamour, show_item_card, show_materia, HotbarView, ItemOrderView, KeybindView, MacroView,
+    PaperDoll, MATERIA_ICON_SIZE,
+};
 
 use egui_file::FileDialog;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 //use egui::scroll_area::ScrollBarVisibility;
 
@@ -17,48 +42,229 @@ use std::path::{Path, PathBuf};
 pub struct PersistedData {
     // Example stuff
     last_opened_file: Option<PathBuf>,
+    recent_files: Vec<PathBuf>,
+    last_export_directory: Option<PathBuf>,
+    pretty_print_json: bool,
+    // Job levels the user has entered, per character folder. The game doesn't save them anywhere
+    // we can read, and validation needs them to check equip levels.
+    job_levels: HashMap<PathBuf, HashMap<u8, u8>>,
 }
 
 pub struct RoarichApp {
     persisted_data: PersistedData,
 
     gearset_config: Option<GearsetList>,
+    gearset_path: Option<PathBuf>,
+    unsaved_changes: bool,
+    // The file as it was when opened or last saved, to tell whether the game has changed it.
+    gearset_file_contents: Option<Vec<u8>>,
+    // Set when the game changed the file while there were unsaved edits. Saving asks first.
+    changed_on_disk: bool,
+    save_conflict_window_open: bool,
+    // A file that was picked while there were unsaved edits, waiting for the user to confirm.
+    pending_open: Option<PathBuf>,
+    rename_buffer: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    file_watcher: Option<FileWatcher>,
+    // Sets that changed the last time the file was reloaded from disk.
+    changed_sets: Vec<u8>,
+
+    status_message: Option<String>,
+    // The message as of the last frame, and when it was first shown, so it can be cleared later.
+    shown_status_message: Option<String>,
+    status_message_since: f64,
 
     data_provider: Option<IronworksProvider>,
 
     open_file_dialog: Option<FileDialog>,
+    save_file_dialog: Option<FileDialog>,
 
     selected_gearset: i8,
+    sidebar_search: String,
+    sidebar_role: Option<Role>,
+    sidebar_group_by_role: bool,
+    // Role groups that were collapsed last frame, which the arrow keys skip over.
+    sidebar_collapsed_roles: Vec<Option<Role>>,
+    sidebar_sort: SidebarSort,
 
     export_window_open: bool,
-    export_window_string: Option<String>,
+    export_window_target: ExportTarget,
+    export_window_string: Option<ExportText>,
+
+    import_window_open: bool,
+    import_window_text: String,
+    import_window_error: Option<String>,
+
+    imported_gearsets: Vec<PortableGearset>,
+    selected_imported_gearset: usize,
+    // How closely each in-game set matches the selected import, by set number, for the sidebar.
+    // None when the file or the import has changed and it needs working out again.
+    sidebar_conformance: Option<Vec<Option<u8>>>,
+
+    // The item shown in the item window, if it's open.
+    inspected_item: Option<PortableItem>,
+    paper_doll: PaperDoll,
+
+    snapshot_watcher: SnapshotWatcher,
+    history_window_open: bool,
+    history_snapshots: Vec<Snapshot>,
+    history_selected: Option<usize>,
+    history_gearsets: Option<GearsetList>,
+    history_diff: Vec<GearsetDiff>,
+
+    current_view: View,
+    macro_view: MacroView,
+    hotbar_view: HotbarView,
+    keybind_view: KeybindView,
+    item_order_view: ItemOrderView,
+}
+
+const RECENT_FILES_PER_CHARACTER: usize = 5;
+
+// How long a message stays in the status bar.
+const STATUS_MESSAGE_SECONDS: f64 = 10.0;
+
+const OPEN_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::O);
+
+/// Which of the character's files is being looked at.
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Gearsets,
+    Macros,
+    Hotbars,
+    Keybinds,
+    ItemOrder,
+}
+
+/// An export, along with its pretty-printed form once that's been asked for.
+struct ExportText {
+    raw: String,
+    pretty: Option<String>,
+}
+
+impl From<String> for ExportText {
+    fn from(raw: String) -> Self {
+        Self { raw, pretty: None }
+    }
+}
+
+impl ExportText {
+    fn get(&mut self, pretty: bool) -> &str {
+        if !pretty {
+            return &self.raw;
+        }
+
+        let raw = &self.raw;
+        self.pretty.get_or_insert_with(|| {
+            serde_json::from_str::<serde_json::Value>(raw)
+                .ok()
+                .and_then(|value| serde_json::to_string_pretty(&value).ok())
+                .unwrap_or_else(|| raw.clone())
+        })
+    }
+}
+
+/// A sidebar column that gearsets can be sorted by.
+#[derive(Clone, Copy, PartialEq)]
+enum SortColumn {
+    SetNumber,
+    Name,
+    ItemLevel,
+}
+
+impl SortColumn {
+    fn display_name(self) -> &'static str {
+        match self {
+            SortColumn::SetNumber => "set number",
+            SortColumn::Name => "name",
+            SortColumn::ItemLevel => "item level",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SidebarSort {
+    column: SortColumn,
+    ascending: bool,
+}
+
+impl Default for SidebarSort {
+    fn default() -> Self {
+        Self {
+            column: SortColumn::SetNumber,
+            ascending: true,
+        }
+    }
+}
+
+impl SidebarSort {
+    fn sort(&self, gearsets: &mut [&Gearset]) {
+        match self.column {
+            SortColumn::SetNumber => gearsets.sort_by_key(|gs| gs.set_number),
+            SortColumn::Name => gearsets.sort_by_key(|gs| gs.name.to_lowercase()),
+            SortColumn::ItemLevel => gearsets.sort_by_key(|gs| gs.average_item_level),
+        }
+        if !self.ascending {
+            gearsets.reverse();
+        }
+    }
+
+    // Clicking the sorted column flips the order, clicking another one sorts by it instead.
+    fn toggle(&mut self, column: SortColumn) {
+        if self.column == column {
+            self.ascending = !self.ascending;
+        } else {
+            self.column = column;
+            self.ascending = true;
+        }
+    }
+
+    fn header(&mut self, ui: &mut egui::Ui, column: SortColumn, label: &str) {
+        let text = match (self.column == column, self.ascending) {
+            (true, true) => format!("{} ⏶", label),
+            (true, false) => format!("{} ⏷", label),
+            (false, _) => label.to_string(),
+        };
+        let widget =
+            egui::Label::new(egui::RichText::new(text).strong()).sense(egui::Sense::click());
+        let response = ui.add(widget);
+        response.widget_info(|| {
+            let order = match (self.column == column, self.ascending) {
+                (true, true) => ", sorted ascending",
+                (true, false) => ", sorted descending",
+                (false, _) => "",
+            };
+            let name = format!("Sort by {}{}", column.display_name(), order);
+            egui::WidgetInfo::labeled(egui::WidgetType::Button, true, name)
+        });
+        if response.clicked() {
+            self.toggle(column);
+        }
+    }
+}
+
+/// A change to the selected gearset, made from the central panel.
+enum GearsetEdit {
+    Rename(String),
+    SetGlamourPlate(u8),
+    MoveUp,
+    MoveDown,
+    Delete,
 }
 
 impl Default for PersistedData {
     fn default() -> Self {
         Self {
             last_opened_file: None,
+            recent_files: Vec::new(),
+            last_export_directory: None,
+            pretty_print_json: false,
+            job_levels: HashMap::new(),
         }
     }
 }
 
-const EQUIPMENT_SLOT_NAMES: [&str; 14] = [
-    "Primary",
-    "Offhand",
-    "Head",
-    "Body",
-    "Hand",
-    "Belt",
-    "Legs",
-    "Feet",
-    "Earrings",
-    "Necklace",
-    "Wrist",
-    "Left Ring",
-    "Right Ring",
-    "Soul Crystal",
-];
-
 impl RoarichApp {
     /// Called once before the first frame.
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
@@ -75,243 +281,1765 @@ impl RoarichApp {
         let data_provider = IronworksProvider::new();
         data_provider.install_bytes_loader(&cc.egui_ctx);
 
-        Self {
+        let mut app = Self {
             persisted_data: persisted,
             gearset_config: None,
+            gearset_path: None,
+            unsaved_changes: false,
+            gearset_file_contents: None,
+            changed_on_disk: false,
+            save_conflict_window_open: false,
+            pending_open: None,
+            rename_buffer: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            file_watcher: None,
+            changed_sets: Vec::new(),
+            status_message: None,
+            shown_status_message: None,
+            status_message_since: 0.0,
             data_provider: Some(data_provider),
             open_file_dialog: None,
+            save_file_dialog: None,
             selected_gearset: -1,
+            sidebar_search: String::new(),
+            sidebar_role: None,
+            sidebar_group_by_role: false,
+            sidebar_collapsed_roles: Vec::new(),
+            sidebar_sort: SidebarSort::default(),
             export_window_open: false,
+            export_window_target: ExportTarget::Xivgear,
             export_window_string: None,
+            import_window_open: false,
+            import_window_text: String::new(),
+            import_window_error: None,
+            imported_gearsets: Vec::new(),
+            selected_imported_gearset: 0,
+            sidebar_conformance: None,
+            inspected_item: None,
+            paper_doll: PaperDoll::default(),
+            snapshot_watcher: SnapshotWatcher::default(),
+            history_window_open: false,
+            history_snapshots: Vec::new(),
+            history_selected: None,
+            history_gearsets: None,
+            history_diff: Vec::new(),
+            current_view: View::Gearsets,
+            macro_view: MacroView::default(),
+            hotbar_view: HotbarView::default(),
+            keybind_view: KeybindView::default(),
+            item_order_view: ItemOrderView::default(),
+        };
+
+        // Pick up where we left off.
+        if let Some(path) = app.persisted_data.last_opened_file.clone() {
+            if path.is_file() {
+                app.open_gearset_file(path, &cc.egui_ctx);
+            }
+        }
+
+        app
+    }
+
+    pub fn open_gearset_file(&mut self, path: PathBuf, ctx: &egui::Context) {
+        let a_gearset = match load_gearsets(&path) {
+            Ok(a_gearset) => a_gearset,
+            Err(err) => {
+                log::error!("unable to open {:?}: {}", path, err);
+                self.status_message = Some(format!("Couldn't open {}: {}", path.display(), err));
+                return;
+            }
+        };
+
+        // Keep a copy of whatever we just opened, and of anything else in the
+        // character folder that changes from here on.
+        if let Err(err) = snapshot_if_changed(&path) {
+            log::error!("unable to snapshot {:?}: {}", path, err);
+        }
+        self.snapshot_watcher.watch(&path);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.file_watcher = match FileWatcher::new(&path, ctx) {
+                Ok(watcher) => Some(watcher),
+                Err(err) => {
+                    log::error!("unable to watch {:?}: {}", path, err);
+                    None
+                }
+            };
         }
+        #[cfg(target_arch = "wasm32")]
+        let _ = ctx;
+        self.changed_sets.clear();
+
+        self.selected_gearset = a_gearset.current;
+        self.gearset_config = Some(a_gearset);
+        self.sidebar_conformance = None;
+        self.gearset_file_contents = std::fs::read(&path).ok();
+        self.gearset_path = Some(path.clone());
+        self.unsaved_changes = false;
+        self.changed_on_disk = false;
+        self.rename_buffer = None;
+        self.add_recent_file(&path);
+        self.persisted_data.last_opened_file = Some(path);
+    }
+
+    fn show_open_dialog(&mut self) {
+        let filter = Box::new({
+            let ext = Some(OsStr::new("DAT"));
+            move |path: &Path| -> bool { path.extension() == ext }
+        });
+        let mut dialog = FileDialog::open_file(self.persisted_data.last_opened_file.clone())
+            .show_files_filter(filter);
+        dialog.open();
+        self.open_file_dialog = Some(dialog);
     }
 
     pub fn run_load_dialog(&mut self, ctx: &egui::Context) {
+        let mut selected_path = None;
         if let Some(dialog) = &mut self.open_file_dialog {
             if dialog.show(ctx).selected() {
-                if let Some(file) = dialog.path() {
-                    let path = file.to_path_buf();
-
-                    let mut dat_file = match DATFile::open(&path) {
-                        Ok(dat_file) => dat_file,
-                        Err(_x) => {
-                            /* we should do something here */
-                            return;
-                        }
-                    };
-                    let a_gearset = match read_gearset(&mut dat_file) {
-                        Ok(a_gearset) => Some(a_gearset),
-                        Err(_x) => None,
-                    };
+                selected_path = dialog.path().map(|path| path.to_path_buf());
+            }
+        }
+
+        if let Some(path) = selected_path {
+            self.open_file_dialog = None;
+            self.request_open_gearset_file(path, ctx);
+        }
+    }
+
+    /// Opens a file, unless that would throw away unsaved edits, in which case the user gets
+    /// asked first.
+    fn request_open_gearset_file(&mut self, path: PathBuf, ctx: &egui::Context) {
+        if self.unsaved_changes {
+            self.pending_open = Some(path);
+        } else {
+            self.open_gearset_file(path, ctx);
+        }
+    }
+
+    fn show_discard_changes_window(&mut self, ctx: &egui::Context) {
+        let Some(path) = &self.pending_open else {
+            return;
+        };
+
+        let mut discard = false;
+        let mut cancel = false;
+        egui::Window::new("Unsaved changes")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Opening {} will throw away your unsaved changes.",
+                    path.display()
+                ));
+                ui.horizontal(|ui| {
+                    discard = ui.button("Discard changes and open").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if discard {
+            if let Some(path) = self.pending_open.take() {
+                self.open_gearset_file(path, ctx);
+            }
+        } else if cancel {
+            self.pending_open = None;
+        }
+    }
+
+    /// Moves `path` to the top of the recent files, dropping the oldest files for the same
+    /// character if there are too many.
+    fn add_recent_file(&mut self, path: &Path) {
+        let recent_files = &mut self.persisted_data.recent_files;
+        recent_files.retain(|x| x != path);
+        recent_files.insert(0, path.to_path_buf());
+
+        let mut per_character: HashMap<Option<String>, usize> = HashMap::new();
+        recent_files.retain(|x| {
+            let count = per_character.entry(character_folder(x)).or_insert(0);
+            *count += 1;
+            *count <= RECENT_FILES_PER_CHARACTER
+        });
+    }
+
+    fn apply_gearset_edit(&mut self, edit: GearsetEdit) {
+        let Some(gsc) = &mut self.gearset_config else {
+            return;
+        };
+        let index = self.selected_gearset as usize;
 
-                    self.gearset_config = a_gearset;
-                    self.selected_gearset = self.gearset_config.as_ref().unwrap().current;
-                    self.persisted_data.last_opened_file = Some(path);
+        match edit {
+            GearsetEdit::Rename(name) => {
+                gsc.gearsets[index].name = truncate_gearset_name(&name);
+            }
+            GearsetEdit::SetGlamourPlate(plate) => {
+                gsc.gearsets[index].glamour_plate = plate;
+            }
+            GearsetEdit::MoveUp | GearsetEdit::MoveDown => {
+                let other = match edit {
+                    GearsetEdit::MoveUp => index.checked_sub(1),
+                    _ => Some(index + 1).filter(|i| *i < gsc.gearsets.len()),
+                };
+                let Some(other) = other else {
+                    return;
+                };
+
+                // The set number is the position in the file, so moving a set means swapping
+                // it with its neighbour (which may well be an empty slot).
+                gsc.gearsets.swap(index, other);
+                gsc.gearsets[index].set_number = index as _;
+                gsc.gearsets[other].set_number = other as _;
+
+                if gsc.current == index as i8 {
+                    gsc.current = other as i8;
+                } else if gsc.current == other as i8 {
+                    gsc.current = index as i8;
                 }
+                self.selected_gearset = other as i8;
+            }
+            GearsetEdit::Delete => {
+                gsc.gearsets[index].flags.remove(GearsetFlags::Exists);
+                // The game's current set can't be deleted, so it's always there to fall back to.
+                self.selected_gearset = gsc.current;
             }
         }
+
+        self.unsaved_changes = true;
+        self.sidebar_conformance = None;
     }
-}
 
-impl eframe::App for RoarichApp {
-    /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, &self.persisted_data);
+    /// Whether the file on disk is different from the one that was opened (or last saved).
+    fn gearset_file_changed(&self) -> bool {
+        let Some(path) = &self.gearset_path else {
+            return false;
+        };
+        std::fs::read(path).ok() != self.gearset_file_contents
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        use egui_extras::{Column, TableBuilder};
+    /// Saves the open file, unless the game has changed it since, in which case the user gets
+    /// asked what to do first. `overwrite` skips asking.
+    fn save_gearsets(&mut self, overwrite: bool) {
+        if !overwrite && (self.changed_on_disk || self.gearset_file_changed()) {
+            self.changed_on_disk = true;
+            self.save_conflict_window_open = true;
+            return;
+        }
 
-        // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
-        // For inspiration and more examples, go to https://emilk.github.io/egui
+        let (Some(path), Some(gsc)) = (&self.gearset_path, &self.gearset_config) else {
+            return;
+        };
 
-        if self.export_window_open {
-            egui::Window::new("Export")
-                .open(&mut self.export_window_open)
-                .resizable([true, true])
-                .show(ctx, |ui| {
-                    ui.horizontal_wrapped(|ui| {
-                        ui.spacing_mut().item_spacing.x = 0.0;
-                        ui.label("Paste the following JSON into ");
-                        ui.hyperlink_to(
-                            "xivgear's import page",
-                            "https://xivgear.app/?page=importsheet",
-                        );
-                    });
-                    ui.separator();
-                    ui.label(self.export_window_string.as_ref().unwrap());
+        match save_gearsets(path, gsc) {
+            Ok(backup_path) => {
+                self.gearset_file_contents = std::fs::read(path).ok();
+                self.unsaved_changes = false;
+                self.changed_on_disk = false;
+                self.status_message = Some(format!(
+                    "Saved {}. The original was backed up to {}.",
+                    path.display(),
+                    backup_path.display()
+                ));
+            }
+            Err(err) => {
+                log::error!("unable to save {:?}: {}", path, err);
+                self.status_message = Some(format!("Couldn't save {}: {}", path.display(), err));
+            }
+        }
+    }
+
+    fn show_save_conflict_window(&mut self, ctx: &egui::Context) {
+        let file_name = self
+            .gearset_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut overwrite = false;
+        let mut reload = false;
+        let mut cancel = false;
+        egui::Window::new("Changed on disk")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} has changed on disk since you started editing it, probably because the \
+                     game saved it. Saving now would overwrite those changes.",
+                    file_name
+                ));
+                ui.horizontal(|ui| {
+                    reload = ui.button("Reload and discard my edits").clicked();
+                    overwrite = ui.button("Overwrite anyway").clicked();
+                    cancel = ui.button("Cancel").clicked();
                 });
+            });
+
+        if overwrite {
+            self.save_conflict_window_open = false;
+            self.save_gearsets(true);
+        } else if reload {
+            self.save_conflict_window_open = false;
+            if let Some(path) = self.gearset_path.clone() {
+                self.open_gearset_file(path, ctx);
+            }
+        } else if cancel {
+            self.save_conflict_window_open = false;
+        }
+    }
+
+    /// Reloads the open file if the game has rewritten it, keeping the same set selected.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_if_changed(&mut self) {
+        let Some(watcher) = &self.file_watcher else {
+            return;
+        };
+        if !watcher.has_changed() {
+            return;
         }
 
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            // The top panel is often a good place for a menu bar:
+        let file_name = watcher
+            .path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
 
-            if self.export_window_open {
-                ui.disable();
+        if self.unsaved_changes {
+            // Our own save can still be on its way through the watcher.
+            if self.gearset_file_changed() {
+                self.changed_on_disk = true;
+                self.status_message = Some(format!(
+                    "{} changed on disk; keeping your unsaved changes for now.",
+                    file_name
+                ));
             }
+            return;
+        }
 
-            egui::menu::bar(ui, |ui| {
-                // NOTE: no File->Quit on web pages!
-                let is_web = cfg!(target_arch = "wasm32");
-                if !is_web {
-                    ui.menu_button("File", |ui| {
-                        if ui.button("Open").clicked() {
-                            let filter = Box::new({
-                                let ext = Some(OsStr::new("DAT"));
-                                move |path: &Path| -> bool { path.extension() == ext }
-                            });
-                            let mut dialog =
-                                FileDialog::open_file(self.persisted_data.last_opened_file.clone())
-                                    .show_files_filter(filter);
-                            dialog.open();
-                            self.open_file_dialog = Some(dialog);
-                        }
+        let new_gearsets = match load_gearsets(watcher.path()) {
+            Ok(new_gearsets) => new_gearsets,
+            Err(err) => {
+                log::error!("unable to reload {:?}: {}", watcher.path(), err);
+                self.status_message = Some(format!("Couldn't reload {}: {}", file_name, err));
+                return;
+            }
+        };
 
-                        if ui.button("Quit").clicked() {
-                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                        }
-                    });
-                    ui.add_space(16.0);
+        let diff = match &self.gearset_config {
+            Some(old_gearsets) => diff_gearsets(old_gearsets, &new_gearsets),
+            None => Vec::new(),
+        };
+        // Keep what's on disk current even if no set changed (the game rewrites the file on
+        // logout, for one), or the next save would think someone else had changed it.
+        self.gearset_file_contents = std::fs::read(watcher.path()).ok();
+
+        // Our own saves come back through here too, and those won't have changed anything.
+        if diff.is_empty() {
+            return;
+        }
+
+        self.changed_sets = diff.iter().map(GearsetDiff::set_number).collect();
+        self.status_message = Some(format!(
+            "Reloaded {}: {}",
+            file_name,
+            diff.iter()
+                .map(GearsetDiff::summary)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        let selected_still_exists = new_gearsets
+            .gearsets
+            .get(self.selected_gearset as usize)
+            .is_some_and(|gs| (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists);
+        if !selected_still_exists {
+            self.selected_gearset = new_gearsets.current;
+            self.rename_buffer = None;
+        }
+        self.gearset_config = Some(new_gearsets);
+        self.sidebar_conformance = None;
+    }
+
+    fn open_history_window(&mut self) {
+        if let Some(path) = &self.gearset_path {
+            self.history_snapshots = list_snapshots(path);
+        }
+        self.history_selected = None;
+        self.history_gearsets = None;
+        self.history_diff.clear();
+        self.history_window_open = true;
+    }
+
+    // Re-lists the snapshots after a new one was taken, keeping the same one selected.
+    fn refresh_history(&mut self) {
+        let Some(path) = &self.gearset_path else {
+            return;
+        };
+        let selected_path = self
+            .history_selected
+            .map(|index| self.history_snapshots[index].path.clone());
+
+        self.history_snapshots = list_snapshots(path);
+        self.history_selected = selected_path.and_then(|selected_path| {
+            self.history_snapshots
+                .iter()
+                .position(|snapshot| snapshot.path == selected_path)
+        });
+    }
+
+    fn select_snapshot(&mut self, index: usize) {
+        self.history_selected = Some(index);
+        self.history_gearsets = None;
+        self.history_diff.clear();
+
+        let path = &self.history_snapshots[index].path;
+        match load_gearsets(path) {
+            Ok(snapshot_gearsets) => {
+                if let Some(gsc) = &self.gearset_config {
+                    self.history_diff = diff_gearsets(&snapshot_gearsets, gsc);
                 }
+                self.history_gearsets = Some(snapshot_gearsets);
+            }
+            Err(err) => {
+                log::error!("unable to open snapshot {:?}: {}", path, err);
+                self.status_message = Some(format!("Couldn't open the snapshot: {}", err));
+            }
+        }
+    }
 
-                egui::widgets::global_theme_preference_buttons(ui);
-            });
+    /// Puts a set from the selected snapshot next to the current one, using the import view.
+    fn compare_with_snapshot(&mut self, set_number: u8) {
+        let (Some(index), Some(snapshot_gearsets)) =
+            (self.history_selected, &self.history_gearsets)
+        else {
+            return;
+        };
+        let Some(gs) = snapshot_gearsets.gearsets.get(set_number as usize) else {
+            return;
+        };
+        // There's nothing to compare against if the set is gone from the open file.
+        let exists_now = self.gearset_config.as_ref().is_some_and(|gsc| {
+            gsc.gearsets
+                .get(set_number as usize)
+                .is_some_and(|gs| (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists)
         });
+        if !exists_now {
+            return;
+        }
+
+        let mut portable = PortableGearset::from(gs);
+        portable.name = format!(
+            "{} ({})",
+            portable.name,
+            display_timestamp(self.history_snapshots[index].taken)
+        );
+        self.imported_gearsets = vec![portable];
+        self.selected_imported_gearset = 0;
+        self.sidebar_conformance = None;
+        self.selected_gearset = set_number as i8;
+    }
+
+    /// Works out the sidebar's match percentages again if anything they depend on has changed.
+    fn update_sidebar_conformance(&mut self) {
+        if self.sidebar_conformance.is_some() {
+            return;
+        }
+        let Some(data_provider) = &self.data_provider else {
+            return;
+        };
+
+        let target = self.imported_gearsets.get(self.selected_imported_gearset);
+        let gearsets = self
+            .gearset_config
+            .as_ref()
+            .map_or(&[][..], |gsc| gsc.gearsets.as_slice());
+        self.sidebar_conformance = Some(
+            gearsets
+                .iter()
+                .map(|gs| {
+                    // Only sets for the same job are worth comparing.
+                    let target = target.filter(|target| {
+                        promote_to_job(target.class_job) == promote_to_job(gs.class_job)
+                    })?;
+                    let conformance =
+                        check_conformance(target, &PortableGearset::from(gs), data_provider);
+                    Some(conformance.percent.round() as u8)
+                })
+                .collect(),
+        );
+    }
+
+    fn gearsets_view(&mut self, ctx: &egui::Context) {
+        self.update_sidebar_conformance();
 
         egui::SidePanel::left("sidebar")
-            .min_width(200.0)
-            .max_width(200.0)
+            .min_width(240.0)
+            .max_width(240.0)
             .show(ctx, |ui| {
-                if self.export_window_open {
+                if self.export_window_open
+                    || self.import_window_open
+                    || self.open_file_dialog.is_some()
+                    || self.save_file_dialog.is_some()
+                {
                     ui.disable();
                 }
 
-                let available_height = ui.available_height();
-                let table = TableBuilder::new(ui)
-                    .striped(true)
-                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                    .column(Column::auto())
-                    .column(Column::exact(16.0))
-                    .column(Column::remainder())
-                    .column(Column::auto())
-                    .column(Column::auto())
-                    .min_scrolled_height(0.0)
-                    .max_scroll_height(available_height)
-                    .sense(egui::Sense::click());
-
-                table.body(|mut body| {
-                    if let Some(gsc) = &self.gearset_config {
-                        for gs in gsc.gearsets.iter() {
-                            if (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists {
-                                body.row(18.0, |mut row| {
-                                    row.col(|ui| {
-                                        ui.label(format!("{}", gs.set_number));
-                                    });
-                                    row.col(|ui| {
-                                        const CLASS_JOB_BASE_ID: u32 = 62000;
-                                        if let Ok(img) =
-                                            self.data_provider.as_ref().unwrap().get_ui_image_by_id(
-                                                CLASS_JOB_BASE_ID + (gs.class_job as u32),
-                                            )
-                                        {
-                                            ui.add(egui::Image::new(img));
-                                        }
-                                    });
-                                    row.col(|ui| {
-                                        ui.label(gs.name.clone());
-                                    });
-                                    row.col(|ui| {
-                                        ui.label(format!("*{}", gs.average_item_level));
-                                    });
-                                    row.col(|ui| {
-                                        ui.label(format!("{}", gs.glamour_plate));
+                ui.horizontal(|ui| {
+                    ui.label("Search:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.sidebar_search)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("sidebar_role")
+                        .selected_text(self.sidebar_role.map_or("All roles", Role::display_name))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.sidebar_role, None, "All roles");
+                            for role in Role::ALL {
+                                ui.selectable_value(
+                                    &mut self.sidebar_role,
+                                    Some(role),
+                                    role.display_name(),
+                                );
+                            }
+                        });
+                    ui.checkbox(&mut self.sidebar_group_by_role, "Group");
+                });
+                ui.separator();
+
+                let Some(gsc) = &self.gearset_config else {
+                    return;
+                };
+
+                let search = self.sidebar_search.to_lowercase();
+                let mut gearsets: Vec<&Gearset> = gsc
+                    .gearsets
+                    .iter()
+                    .filter(|gs| (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists)
+                    .filter(|gs| gs.name.to_lowercase().contains(&search))
+                    .filter(|gs| {
+                        self.sidebar_role
+                            .map_or(true, |role| class_job_role(gs.class_job) == Some(role))
+                    })
+                    .collect();
+                self.sidebar_sort.sort(&mut gearsets);
+                if self.sidebar_group_by_role {
+                    // Keep the arrow keys going through sets in the order they're shown.
+                    gearsets.sort_by_key(|gs| {
+                        let role = class_job_role(gs.class_job);
+                        (role.is_none(), role)
+                    });
+                }
+
+                // The arrow keys move the selection up and down the list, through the sets that
+                // can be seen: none while the sidebar is disabled, and none in collapsed groups.
+                let mut clicked = None;
+                if ui.is_enabled() && no_widget_focused(ui.ctx()) {
+                    let visible: Vec<&Gearset> = gearsets
+                        .iter()
+                        .copied()
+                        .filter(|gs| {
+                            !self.sidebar_group_by_role
+                                || !self
+                                    .sidebar_collapsed_roles
+                                    .contains(&class_job_role(gs.class_job))
+                        })
+                        .collect();
+
+                    let step = ui.input_mut(|i| {
+                        if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+                            1
+                        } else if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+                            -1
+                        } else {
+                            0
+                        }
+                    });
+                    if step != 0 {
+                        clicked = step_selection(&visible, self.selected_gearset, step);
+                    }
+                }
+
+                let rows = GearsetRows {
+                    data_provider: self.data_provider.as_ref().unwrap(),
+                    changed_sets: &self.changed_sets,
+                    conformance: self.sidebar_conformance.as_deref().unwrap_or_default(),
+                    selected: clicked.map_or(self.selected_gearset, |set_number| set_number as i8),
+                    scroll_to_selected: clicked.is_some(),
+                };
+
+                if self.sidebar_group_by_role {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        // Adventurer sets don't have a role, so they go last.
+                        let roles = Role::ALL.iter().map(|role| Some(*role)).chain([None]);
+                        for role in roles {
+                            let group: Vec<&Gearset> = gearsets
+                                .iter()
+                                .copied()
+                                .filter(|gs| class_job_role(gs.class_job) == role)
+                                .collect();
+                            if group.is_empty() {
+                                continue;
+                            }
+
+                            let title = role.map_or("Other", Role::display_name);
+                            let response =
+                                egui::CollapsingHeader::new(format!("{} ({})", title, group.len()))
+                                    .default_open(true)
+                                    .show(ui, |ui| {
+                                        rows.show(ui, title, &group, &mut self.sidebar_sort, false)
                                     });
+                            if let Some(set_number) = response.body_returned.flatten() {
+                                clicked = Some(set_number);
+                            }
 
-                                    if row.response().clicked() {
-                                        self.selected_gearset = gs.set_number as i8;
-                                    }
-                                });
+                            let collapsed = response.openness == 0.0;
+                            self.sidebar_collapsed_roles.retain(|r| *r != role);
+                            if collapsed {
+                                self.sidebar_collapsed_roles.push(role);
                             }
                         }
-                    }
-                });
+                    });
+                } else if let Some(set_number) =
+                    rows.show(ui, "all", &gearsets, &mut self.sidebar_sort, true)
+                {
+                    clicked = Some(set_number);
+                }
+
+                if let Some(set_number) = clicked {
+                    self.selected_gearset = set_number as i8;
+                    self.rename_buffer = None;
+                    self.changed_sets.retain(|x| *x != set_number);
+                }
             });
 
+        // Enter exports the selected set and Ctrl+C copies that export straight to the clipboard.
+        let selected = self
+            .gearset_config
+            .as_ref()
+            .and_then(|gsc| gsc.gearsets.get(self.selected_gearset as usize));
+        if let Some(gs) = selected {
+            if !self.export_window_open && !self.import_window_open && no_widget_focused(ctx) {
+                let data_provider = self.data_provider.as_ref().unwrap();
+                if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)) {
+                    let (target, text) = quick_export(gs, data_provider);
+                    self.export_window_open = true;
+                    self.export_window_target = target;
+                    self.export_window_string = Some(text.into());
+                } else if copy_pressed(ctx) {
+                    let (_, text) = quick_export(gs, data_provider);
+                    ctx.copy_text(text);
+                    self.status_message = Some(format!("Copied {} to the clipboard", gs.name));
+                }
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             // The central panel the region left after adding TopPanel's and SidePanel's
 
-            if self.export_window_open {
+            if self.export_window_open || self.import_window_open {
                 ui.disable();
             }
 
-            if let Some(gsc) = &self.gearset_config {
-                let index = self.selected_gearset as usize;
-                let gs = &gsc.gearsets[index];
+            let data_provider = self.data_provider.as_ref().unwrap();
+            let gearset = self
+                .gearset_config
+                .as_ref()
+                .map(|gsc| &gsc.gearsets[self.selected_gearset as usize]);
 
-                const CLASS_JOB_FRAMED_BASE_ID: u32 = 62100;
-                if let Ok(img) = self
-                    .data_provider
-                    .as_ref()
-                    .unwrap()
-                    .get_ui_image_by_id(CLASS_JOB_FRAMED_BASE_ID + (gs.class_job as u32))
-                {
-                    ui.add(egui::Image::new(img).max_width(32.0));
-                }
+            let mut gearset_edit = None;
 
-                ui.heading(gs.name.clone());
+            // Show an imported gearset side by side with the in-game one.
+            let column_count = if self.imported_gearsets.is_empty() {
+                1
+            } else {
+                2
+            };
+            ui.columns(column_count, |columns| {
+                if let Some(gs) = gearset {
+                    let ui = &mut columns[0];
 
-                if ui.button("Export to xivgear").clicked() {
-                    self.export_window_open = true;
-                    self.export_window_string =
-                        Some(get_xivgear_json(&gs, self.data_provider.as_ref().unwrap()));
-                }
+                    show_gearset_heading(ui, data_provider, gs.class_job, &gs.name);
 
-                let table = TableBuilder::new(ui)
-                    .striped(true)
-                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                    .column(Column::auto())
-                    .column(Column::auto())
-                    .column(Column::remainder());
-
-                table.body(|mut body| {
-                    for (i, eq) in gs.equipment.iter().enumerate() {
-                        // Skip belt slot if it's empty.
-                        if i == 5 && eq.item_id == 0 {
-                            continue;
+                    ui.horizontal(|ui| {
+                        match &mut self.rename_buffer {
+                            Some(buffer) => {
+                                ui.text_edit_singleline(buffer);
+                                if ui.button("OK").clicked() {
+                                    gearset_edit = Some(GearsetEdit::Rename(buffer.clone()));
+                                    self.rename_buffer = None;
+                                } else if ui.button("Cancel").clicked() {
+                                    self.rename_buffer = None;
+                                }
+                            }
+                            None => {
+                                if ui.button("Rename").clicked() {
+                                    self.rename_buffer = Some(gs.name.clone());
+                                }
+                            }
                         }
 
-                        let item = self
-                            .data_provider
-                            .as_ref()
-                            .unwrap()
-                            .get_item(eq.item_id)
-                            .unwrap();
-
-                        body.row(32.0, |mut row| {
-                            row.col(|ui| {
-                                ui.label(format!("{}", EQUIPMENT_SLOT_NAMES[i]));
-                            });
-                            row.col(|ui| {
-                                if let Ok(img) =
-                                    self.data_provider.as_ref().unwrap().get_image(&item.icon)
-                                {
-                                    ui.add(egui::Image::new(img));
-                                }
-                            });
-                            row.col(|ui| {
-                                ui.label(format!("{}", item.name));
-                            });
-                        });
-                    }
-                });
-            }
+                        ui.label("Glamour plate");
+                        let mut glamour_plate = gs.glamour_plate;
+                        if ui
+                            .add(egui::DragValue::new(&mut glamour_plate).range(0..=20))
+                            .changed()
+                        {
+                            gearset_edit = Some(GearsetEdit::SetGlamourPlate(glamour_plate));
+                        }
 
-            self.run_load_dialog(ctx);
+                        if ui.button("Move up").clicked() {
+                            gearset_edit = Some(GearsetEdit::MoveUp);
+                        }
+                        if ui.button("Move down").clicked() {
+                            gearset_edit = Some(GearsetEdit::MoveDown);
+                        }
+                        let is_current = self.gearset_config.as_ref().map(|gsc| gsc.current)
+                            == Some(gs.set_number as i8);
+                        if ui
+                            .add_enabled(!is_current, egui::Button::new("Delete"))
+                            .clicked()
+                        {
+                            gearset_edit = Some(GearsetEdit::Delete);
+                        }
+                    });
 
-            //ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
-            //   /egui::warn_if_debug_build(ui);
-            //});
+                    let kind = class_job_kind(gs.class_job);
+                    ui.horizontal(|ui| {
+                        // xivgear only does combat jobs.
+                        if kind == ClassJobKind::Combat && ui.button("Export to xivgear").clicked()
+                        {
+                            self.export_window_open = true;
+                            self.export_window_target = ExportTarget::Xivgear;
+                            self.export_window_string =
+                                Some(get_xivgear_json(gs, data_provider).into());
+                        }
+                        if ui.button("Export to Teamcraft").clicked() {
+                            self.export_window_open = true;
+                            self.export_window_target = ExportTarget::Teamcraft;
+                            self.export_window_string =
+                                Some(get_teamcraft_json(gs, data_provider).into());
+                        }
+                        if ui.button("Share code").clicked() {
+                            self.export_window_open = true;
+                            self.export_window_target = ExportTarget::ShareCode;
+                            self.export_window_string =
+                                Some(get_share_code(&PortableGearset::from(gs)).into());
+                        }
+                        ui.menu_button("Report", |ui| {
+                            if let Some(target) = report_menu(ui) {
+                                self.export_window_open = true;
+                                self.export_window_target = target;
+                                self.export_window_string =
+                                    Some(get_report(target, &[gs], data_provider).into());
+                            }
+                        });
+                    });
+
+                    let shown_params: &[i32] = match kind {
+                        ClassJobKind::Combat => &[],
+                        ClassJobKind::Crafter => &CRAFTER_PARAMS,
+                        ClassJobKind::Gatherer => &GATHERER_PARAMS,
+                    };
+                    if !shown_params.is_empty() {
+                        let totals = gearset_stat_totals(gs, data_provider);
+                        ui.horizontal(|ui| {
+                            for param in shown_params {
+                                ui.label(format!(
+                                    "{} {}",
+                                    base_param_display_name(*param),
+                                    totals.get(param).copied().unwrap_or(0)
+                                ));
+                            }
+                        });
+                    }
+
+                    // Classes share their level with the job they turn into.
+                    let job = promote_to_job(gs.class_job);
+                    let level_cap = class_job_level_cap(job);
+                    let character_dir = self.gearset_path.as_deref().and_then(Path::parent);
+                    let mut level = character_dir
+                        .and_then(|dir| self.persisted_data.job_levels.get(dir))
+                        .and_then(|levels| levels.get(&job).copied())
+                        .unwrap_or(level_cap);
+                    if let Some(dir) = character_dir {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{} level", class_job_abbreviation(job)));
+                            if ui
+                                .add(egui::DragValue::new(&mut level).range(1..=level_cap))
+                                .on_hover_text(
+                                    "The game doesn't save job levels where we can read them. \
+                                     Equip levels are checked against this.",
+                                )
+                                .changed()
+                            {
+                                self.persisted_data
+                                    .job_levels
+                                    .entry(dir.to_path_buf())
+                                    .or_default()
+                                    .insert(job, level);
+                            }
+                        });
+                    }
+
+                    let portable = PortableGearset::from(gs);
+                    for problem in validate_gearset(&portable, level, data_provider) {
+                        ui.colored_label(ui.visuals().warn_fg_color, problem.to_string());
+                    }
+
+                    if let Some(target) = self.imported_gearsets.get(self.selected_imported_gearset)
+                    {
+                        if promote_to_job(target.class_job) == promote_to_job(gs.class_job) {
+                            show_conformance(ui, data_provider, target, gs);
+                        }
+                    }
+
+                    let equipment = PortableGearset::from(gs).equipment;
+                    if let Some(item) =
+                        show_equipment_table(ui, data_provider, "gearset_equipment", &equipment)
+                    {
+                        self.inspected_item = Some(item);
+                    }
+
+                    ui.add_space(8.0);
+                    ui.collapsing("Glamour", |ui| {
+                        show_glamour(ui, data_provider, gs);
+                    });
+                    ui.collapsing("Preview", |ui| {
+                        self.paper_doll.show(ui, data_provider, gs);
+                    });
+                }
+
+                if !self.imported_gearsets.is_empty() {
+                    let ui = &mut columns[1];
+
+                    ui.horizontal(|ui| {
+                        // xivgear sheets can hold several sets.
+                        if self.imported_gearsets.len() > 1 {
+                            egui::ComboBox::from_id_salt("imported_gearset")
+                                .selected_text(
+                                    &self.imported_gearsets[self.selected_imported_gearset].name,
+                                )
+                                .show_ui(ui, |ui| {
+                                    for (i, imported) in self.imported_gearsets.iter().enumerate() {
+                                        if ui
+                                            .selectable_value(
+                                                &mut self.selected_imported_gearset,
+                                                i,
+                                                &imported.name,
+                                            )
+                                            .changed()
+                                        {
+                                            self.sidebar_conformance = None;
+                                        }
+                                    }
+                                });
+                        }
+                        if ui.button("Close").clicked() {
+                            self.imported_gearsets.clear();
+                            self.sidebar_conformance = None;
+                        }
+                    });
+                    let Some(imported) = self.imported_gearsets.get(self.selected_imported_gearset)
+                    else {
+                        return;
+                    };
+
+                    show_gearset_heading(ui, data_provider, imported.class_job, &imported.name);
+
+                    if let Some(item) = show_equipment_table(
+                        ui,
+                        data_provider,
+                        "imported_equipment",
+                        &imported.equipment,
+                    ) {
+                        self.inspected_item = Some(item);
+                    }
+                }
+            });
+
+            if let Some(edit) = gearset_edit {
+                self.apply_gearset_edit(edit);
+            }
+
+            //ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+            //   /egui::warn_if_debug_build(ui);
+            //});
         });
     }
+
+    // The other character files live next to GEARSET.DAT, so start looking there.
+    fn character_dir(&self) -> Option<PathBuf> {
+        self.gearset_path
+            .as_deref()
+            .or(self.persisted_data.last_opened_file.as_deref())
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+    }
+
+    fn macros_view(&mut self, ctx: &egui::Context) {
+        let start_dir = self.character_dir();
+
+        if let Some((target, text)) = self.macro_view.show(
+            ctx,
+            self.data_provider.as_ref().unwrap(),
+            start_dir.as_deref(),
+        ) {
+            self.export_window_open = true;
+            self.export_window_target = target;
+            self.export_window_string = Some(text.into());
+        }
+    }
+
+    fn hotbars_view(&mut self, ctx: &egui::Context) {
+        let start_dir = self.character_dir();
+
+        self.hotbar_view.show(
+            ctx,
+            self.data_provider.as_ref().unwrap(),
+            start_dir.as_deref(),
+        );
+    }
+
+    fn keybinds_view(&mut self, ctx: &egui::Context) {
+        let start_dir = self.character_dir();
+
+        if let Some((target, text)) = self.keybind_view.show(ctx, start_dir.as_deref()) {
+            self.export_window_open = true;
+            self.export_window_target = target;
+            self.export_window_string = Some(text.into());
+        }
+    }
+
+    fn item_order_view(&mut self, ctx: &egui::Context) {
+        let start_dir = self.character_dir();
+
+        self.item_order_view.show(
+            ctx,
+            self.data_provider.as_ref().unwrap(),
+            self.gearset_config.as_ref(),
+            start_dir.as_deref(),
+        );
+    }
+
+    /// The export as it should be shown and saved, taking the pretty-print setting into account.
+    fn export_text(&mut self) -> &str {
+        let pretty = self.export_window_target.is_json() && self.persisted_data.pretty_print_json;
+        self.export_window_string
+            .as_mut()
+            .map_or("", |export| export.get(pretty))
+    }
+
+    fn save_export(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::download::download_text(
+                self.export_window_target.default_file_name(),
+                self.export_text(),
+            );
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut dialog =
+                FileDialog::save_file(self.persisted_data.last_export_directory.clone())
+                    .default_filename(self.export_window_target.default_file_name());
+            dialog.open();
+            self.save_file_dialog = Some(dialog);
+        }
+    }
+
+    pub fn run_save_dialog(&mut self, ctx: &egui::Context) {
+        let mut selected_path = None;
+        if let Some(dialog) = &mut self.save_file_dialog {
+            if dialog.show(ctx).selected() {
+                selected_path = dialog.path().map(|path| path.to_path_buf());
+            }
+        }
+
+        if let Some(path) = selected_path {
+            match std::fs::write(&path, self.export_text()) {
+                Ok(()) => {
+                    self.status_message = Some(format!("Saved export to {}", path.display()));
+                }
+                Err(err) => {
+                    log::error!("unable to save export to {:?}: {}", path, err);
+                    self.status_message = Some(format!(
+                        "Couldn't save export to {}: {}",
+                        path.display(),
+                        err
+                    ));
+                }
+            }
+            self.persisted_data.last_export_directory = path.parent().map(Path::to_path_buf);
+            self.save_file_dialog = None;
+        }
+    }
+}
+
+// Keyboard shortcuts only apply when no widget would take the keys itself.
+fn no_widget_focused(ctx: &egui::Context) -> bool {
+    ctx.memory(|m| m.focused().is_none())
+}
+
+// Ctrl+C arrives as a copy event rather than a key press.
+fn copy_pressed(ctx: &egui::Context) -> bool {
+    ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)))
+}
+
+/// The set listed `step` rows away from the selected one, or the first listed set if the selected
+/// one isn't listed.
+fn step_selection(gearsets: &[&Gearset], selected: i8, step: isize) -> Option<u8> {
+    let index = match gearsets
+        .iter()
+        .position(|gs| gs.set_number as i8 == selected)
+    {
+        Some(position) => position.checked_add_signed(step)?,
+        None => 0,
+    };
+    gearsets.get(index).map(|gs| gs.set_number as u8)
+}
+
+/// The export Enter and Ctrl+C use: xivgear for combat jobs, Teamcraft for everything else.
+fn quick_export(gs: &Gearset, data_provider: &IronworksProvider) -> (ExportTarget, String) {
+    match class_job_kind(gs.class_job) {
+        ClassJobKind::Combat => (ExportTarget::Xivgear, get_xivgear_json(gs, data_provider)),
+        _ => (
+            ExportTarget::Teamcraft,
+            get_teamcraft_json(gs, data_provider),
+        ),
+    }
+}
+
+/// Shows the recent files grouped by character, returning the one that was picked.
+fn recent_files_menu(ui: &mut egui::Ui, recent_files: &[PathBuf]) -> Option<PathBuf> {
+    let mut characters: Vec<Option<String>> = Vec::new();
+    for path in recent_files {
+        let character = character_folder(path);
+        if !characters.contains(&character) {
+            characters.push(character);
+        }
+    }
+
+    let mut picked = None;
+    for character in characters {
+        ui.label(egui::RichText::new(character.as_deref().unwrap_or("Unknown character")).strong());
+
+        for path in recent_files
+            .iter()
+            .filter(|x| character_folder(x) == character)
+        {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let modified = std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .map(display_timestamp)
+                .unwrap_or_else(|_| "missing".to_string());
+
+            if ui
+                .button(format!("{} ({})", file_name, modified))
+                .on_hover_text(path.display().to_string())
+                .clicked()
+            {
+                picked = Some(path.clone());
+            }
+        }
+    }
+
+    if picked.is_some() {
+        ui.close_menu();
+    }
+    picked
+}
+
+/// Shows the report formats as menu entries, returning the one that was picked.
+fn report_menu(ui: &mut egui::Ui) -> Option<ExportTarget> {
+    let mut picked = None;
+    if ui.button("Text").clicked() {
+        picked = Some(ExportTarget::TextReport);
+    }
+    if ui.button("Markdown").clicked() {
+        picked = Some(ExportTarget::MarkdownReport);
+    }
+    if ui.button("CSV").clicked() {
+        picked = Some(ExportTarget::CsvReport);
+    }
+    if picked.is_some() {
+        ui.close_menu();
+    }
+    picked
+}
+
+/// The sets that are actually in use, leaving out the empty entries GEARSET.DAT always has.
+fn existing_gearsets(gearsets: &GearsetList) -> Vec<&Gearset> {
+    gearsets
+        .gearsets
+        .iter()
+        .filter(|gs| (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists)
+        .collect()
+}
+
+fn get_report(
+    target: ExportTarget,
+    gearsets: &[&Gearset],
+    data_provider: &IronworksProvider,
+) -> String {
+    match target {
+        ExportTarget::MarkdownReport => get_markdown_report(gearsets, data_provider),
+        ExportTarget::CsvReport => get_csv_report(gearsets, data_provider),
+        _ => get_text_report(gearsets, data_provider),
+    }
+}
+
+fn materia_name(data_provider: &IronworksProvider, materia_type: u16, materia_grade: u8) -> String {
+    data_provider
+        .get_materia(materia_type as u32)
+        .ok()
+        .and_then(|materia| materia.item_id.get(materia_grade as usize).copied())
+        .and_then(|item_id| data_provider.get_item(ItemId(item_id)).ok())
+        .map(|item| item.name)
+        .unwrap_or_else(|| format!("Materia {}/{}", materia_type, materia_grade))
+}
+
+fn show_conformance(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    target: &PortableGearset,
+    gearset: &Gearset,
+) {
+    let conformance = check_conformance(target, &PortableGearset::from(gearset), data_provider);
+
+    ui.collapsing(
+        format!("{:.0}% of {}", conformance.percent, target.name),
+        |ui| {
+            let mut all_good = true;
+            for (i, slot) in conformance.slots.iter().enumerate() {
+                let mut problems = Vec::new();
+                match slot.status {
+                    SlotStatus::Match | SlotStatus::NotInTarget => {}
+                    SlotStatus::WrongQuality => problems.push("NQ, could be HQ".to_string()),
+                    SlotStatus::WrongItem => problems.push("wrong item".to_string()),
+                    SlotStatus::Missing => problems.push("empty".to_string()),
+                }
+                for (t, g) in slot.missing_materia.iter() {
+                    problems.push(format!("missing {}", materia_name(data_provider, *t, *g)));
+                }
+                for (t, g) in slot.wrong_materia.iter() {
+                    problems.push(format!("extra {}", materia_name(data_provider, *t, *g)));
+                }
+
+                if !problems.is_empty() {
+                    all_good = false;
+                    ui.label(format!(
+                        "{}: {}",
+                        EquipSlot::ALL[i].display_name(),
+                        problems.join(", ")
+                    ));
+                }
+            }
+            if all_good {
+                ui.label("Matches the target set.");
+            }
+        },
+    );
+}
+
+fn show_gearset_heading(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    class_job: u8,
+    name: &str,
+) {
+    const CLASS_JOB_FRAMED_BASE_ID: u32 = 62100;
+    if let Ok(img) = data_provider.get_ui_image_by_id(CLASS_JOB_FRAMED_BASE_ID + (class_job as u32))
+    {
+        ui.add(egui::Image::new(img).max_width(32.0))
+            .widget_info(|| {
+                egui::WidgetInfo::labeled(egui::WidgetType::Image, true, class_job_name(class_job))
+            });
+    }
+
+    ui.heading(name);
+}
+
+/// What the sidebar needs to draw its rows of in-game gearsets.
+struct GearsetRows<'a> {
+    data_provider: &'a IronworksProvider,
+    changed_sets: &'a [u8],
+    // How closely each set matches the imported gearset, by set number.
+    conformance: &'a [Option<u8>],
+    selected: i8,
+    // Set when the selection moved with the keyboard, so it doesn't end up out of view.
+    scroll_to_selected: bool,
+}
+
+impl GearsetRows<'_> {
+    /// Shows a table of gearsets, returning the number of the one that was clicked on, if any.
+    fn show(
+        &self,
+        ui: &mut egui::Ui,
+        id_salt: &str,
+        gearsets: &[&Gearset],
+        sort: &mut SidebarSort,
+        scroll: bool,
+    ) -> Option<u8> {
+        use egui_extras::{Column, TableBuilder};
+
+        let available_height = ui.available_height();
+        let table = TableBuilder::new(ui)
+            .id_salt(id_salt)
+            .striped(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::auto())
+            .column(Column::exact(16.0))
+            .column(Column::remainder())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .vscroll(scroll)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .sense(egui::Sense::click());
+
+        let mut clicked = None;
+        table
+            .header(18.0, |mut header| {
+                header.col(|ui| sort.header(ui, SortColumn::SetNumber, "#"));
+                header.col(|_| {});
+                header.col(|ui| sort.header(ui, SortColumn::Name, "Name"));
+                header.col(|ui| sort.header(ui, SortColumn::ItemLevel, "iLvl"));
+                header.col(|_| {});
+                header.col(|_| {});
+            })
+            .body(|mut body| {
+                for gs in gearsets {
+                    body.row(18.0, |mut row| {
+                        let is_selected = gs.set_number as i8 == self.selected;
+                        row.set_selected(is_selected);
+                        row.col(|ui| {
+                            ui.label(format!("{}", gs.set_number));
+                        });
+                        row.col(|ui| {
+                            const CLASS_JOB_BASE_ID: u32 = 62000;
+                            if let Ok(img) = self
+                                .data_provider
+                                .get_ui_image_by_id(CLASS_JOB_BASE_ID + (gs.class_job as u32))
+                            {
+                                ui.add(egui::Image::new(img)).widget_info(|| {
+                                    egui::WidgetInfo::labeled(
+                                        egui::WidgetType::Image,
+                                        true,
+                                        class_job_name(gs.class_job),
+                                    )
+                                });
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(gs.name.clone());
+                            if self.changed_sets.contains(&(gs.set_number as u8)) {
+                                ui.weak("●")
+                                    .on_hover_text("Changed on disk")
+                                    .widget_info(|| {
+                                        egui::WidgetInfo::labeled(
+                                            egui::WidgetType::Label,
+                                            true,
+                                            "Changed on disk",
+                                        )
+                                    });
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("*{}", gs.average_item_level));
+                        });
+                        row.col(|ui| {
+                            if let Some(Some(percent)) =
+                                self.conformance.get(gs.set_number as usize)
+                            {
+                                ui.label(format!("{}%", percent));
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(format!("{}", gs.glamour_plate));
+                        });
+
+                        let response = row.response();
+                        response.widget_info(|| {
+                            let label = format!(
+                                "Set {}, {}, {}, item level {}",
+                                gs.set_number,
+                                gs.name,
+                                class_job_name(gs.class_job),
+                                gs.average_item_level
+                            );
+                            egui::WidgetInfo::selected(
+                                egui::WidgetType::SelectableLabel,
+                                true,
+                                is_selected,
+                                label,
+                            )
+                        });
+                        if is_selected && self.scroll_to_selected {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            clicked = Some(gs.set_number as u8);
+                        }
+                    });
+                }
+            });
+
+        clicked
+    }
+}
+
+/// Shows a gearset's equipment, returning the item that was clicked on, if any.
+fn show_equipment_table(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    id_salt: &str,
+    equipment: &[PortableItem],
+) -> Option<PortableItem> {
+    use egui_extras::{Column, TableBuilder};
+
+    let table = TableBuilder::new(ui)
+        .id_salt(id_salt)
+        .striped(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::remainder())
+        .sense(egui::Sense::click());
+
+    let mut clicked = None;
+    table.body(|mut body| {
+        for (slot, eq) in EquipSlot::ALL.iter().zip(equipment.iter()) {
+            let item_id = eq.item_id;
+            // Skip belt slot if it's empty.
+            if *slot == EquipSlot::Waist && item_id.is_empty() {
+                continue;
+            }
+
+            // Imported sets can refer to items that don't exist (anymore), so don't
+            // assume the lookup succeeds.
+            let item = data_provider.get_item(item_id);
+
+            let melds = eq.melds();
+            // One line per meld, so the row grows to fit them.
+            let row_height = (melds.len() as f32 * (MATERIA_ICON_SIZE + 2.0)).max(32.0);
+
+            body.row(row_height, |mut row| {
+                row.col(|ui| {
+                    ui.label(slot.display_name());
+                });
+                row.col(|ui| {
+                    if let Ok(item) = &item {
+                        if let Ok(img) = data_provider.get_image(&item.icon) {
+                            ui.add(egui::Image::new(img)).widget_info(|| {
+                                egui::WidgetInfo::labeled(egui::WidgetType::Image, true, &item.name)
+                            });
+                        }
+                    }
+                });
+                row.col(|ui| match &item {
+                    Ok(item) => {
+                        ui.label(format!("{}", item.name));
+                    }
+                    Err(_) => {
+                        ui.label(format!("Unknown item {}", item_id));
+                    }
+                });
+                row.col(|ui| {
+                    ui.vertical(|ui| {
+                        ui.spacing_mut().item_spacing.y = 2.0;
+                        for (materia_type, grade) in melds.iter() {
+                            show_materia(ui, data_provider, *materia_type, *grade);
+                        }
+                    });
+                });
+
+                // Read out the slot along with the item, since they're in separate columns.
+                let response = row.response();
+                response.widget_info(|| {
+                    let name = match &item {
+                        Ok(item) => item.name.clone(),
+                        Err(_) => format!("Unknown item {}", item_id),
+                    };
+                    let label = format!("{}: {}", slot.display_name(), name);
+                    egui::WidgetInfo::labeled(egui::WidgetType::Button, true, label)
+                });
+                if let Ok(item) = &item {
+                    if response.clicked() {
+                        clicked = Some(eq.clone());
+                    }
+                    response.on_hover_ui(|ui| {
+                        show_item_card(ui, data_provider, item, eq, "gear_table_hover")
+                    });
+                }
+            });
+        }
+    });
+
+    clicked
+}
+
+impl eframe::App for RoarichApp {
+    /// Called by the frame work to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.persisted_data);
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per second.
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
+        // For inspiration and more examples, go to https://emilk.github.io/egui
+
+        if self.export_window_open {
+            // Cloned so the window can borrow the rest of self, but only formatted once.
+            let export_text = self.export_text().to_owned();
+            let mut copy_clicked = false;
+            let mut save_clicked = false;
+            egui::Window::new("Export")
+                .open(&mut self.export_window_open)
+                .resizable([true, true])
+                .show(ctx, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        match self.export_window_target.import_page() {
+                            Some((page_name, page_url)) => {
+                                ui.label("Paste the following JSON into ");
+                                ui.hyperlink_to(page_name, page_url);
+                            }
+                            None => {
+                                ui.label(self.export_window_target.instructions());
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button("Copy to clipboard").clicked() {
+                            copy_clicked = true;
+                        }
+                        if ui.button("Save as…").clicked() {
+                            save_clicked = true;
+                        }
+                        if self.export_window_target.is_json() {
+                            ui.checkbox(&mut self.persisted_data.pretty_print_json, "Pretty-print");
+                        }
+                    });
+                    ui.separator();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut export_text.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                });
+
+            // Copy the whole export unless the text box has focus, so a selection can be copied.
+            if copy_clicked || (no_widget_focused(ctx) && copy_pressed(ctx)) {
+                ctx.copy_text(export_text);
+            }
+            if save_clicked {
+                self.save_export();
+            }
+        }
+
+        if self.save_conflict_window_open {
+            self.show_save_conflict_window(ctx);
+        }
+        self.show_discard_changes_window(ctx);
+
+        if self.import_window_open {
+            let mut import_clicked = false;
+            egui::Window::new("Import")
+                .open(&mut self.import_window_open)
+                .resizable([true, true])
+                .show(ctx, |ui| {
+                    ui.label("Paste a roarich share code, or xivgear or Etro JSON:");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.import_window_text)
+                            .desired_width(f32::INFINITY),
+                    );
+                    if let Some(error) = &self.import_window_error {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    import_clicked = ui.button("Import").clicked();
+                });
+
+            if import_clicked {
+                match parse_import(
+                    &self.import_window_text,
+                    self.data_provider.as_ref().unwrap(),
+                ) {
+                    Ok(gearsets) => {
+                        self.imported_gearsets = gearsets;
+                        self.selected_imported_gearset = 0;
+                        self.sidebar_conformance = None;
+                        self.import_window_error = None;
+                        self.import_window_open = false;
+                    }
+                    Err(err) => {
+                        self.import_window_error = Some(err.to_string());
+                    }
+                }
+            }
+        }
+
+        if let Some(equipped) = &self.inspected_item {
+            let mut open = true;
+            egui::Window::new("Item")
+                .open(&mut open)
+                .resizable([false, false])
+                .show(ctx, |ui| {
+                    let data_provider = self.data_provider.as_ref().unwrap();
+                    match data_provider.get_item(equipped.item_id) {
+                        Ok(item) => {
+                            show_item_card(ui, data_provider, &item, equipped, "inspected_item")
+                        }
+                        Err(_) => {
+                            ui.label(format!("Unknown item {}", equipped.item_id));
+                        }
+                    }
+                });
+            if !open {
+                self.inspected_item = None;
+            }
+        }
+
+        if self.history_window_open {
+            let mut selected_snapshot = None;
+            let mut compare_set = None;
+            egui::Window::new("History")
+                .open(&mut self.history_window_open)
+                .resizable([true, true])
+                .show(ctx, |ui| {
+                    if self.history_snapshots.is_empty() {
+                        ui.label("No snapshots yet.");
+                    }
+
+                    ui.columns(2, |columns| {
+                        egui::ScrollArea::vertical()
+                            .id_salt("history_snapshots")
+                            .show(&mut columns[0], |ui| {
+                                for (i, snapshot) in self.history_snapshots.iter().enumerate() {
+                                    if ui
+                                        .selectable_label(
+                                            self.history_selected == Some(i),
+                                            display_timestamp(snapshot.taken),
+                                        )
+                                        .clicked()
+                                    {
+                                        selected_snapshot = Some(i);
+                                    }
+                                }
+                            });
+
+                        let ui = &mut columns[1];
+                        if self.history_selected.is_none() {
+                            ui.label("Pick a snapshot to compare it with the open file.");
+                        } else if self.history_diff.is_empty() {
+                            ui.label("Same as the open file.");
+                        }
+                        for diff in self.history_diff.iter() {
+                            match diff {
+                                GearsetDiff::Added(set_number) => {
+                                    ui.label(format!("#{}: added since", set_number));
+                                }
+                                GearsetDiff::Removed(set_number) => {
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!("#{}: removed since", set_number));
+                                        // There's no set in the open file to show it next to.
+                                        ui.add_enabled(false, egui::Button::new("Show").small())
+                                            .on_disabled_hover_text("Not in the open file");
+                                    });
+                                }
+                                GearsetDiff::Changed(set_number, changes) => {
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!(
+                                            "#{}: {}",
+                                            set_number,
+                                            changes.join(", ")
+                                        ));
+                                        if ui.small_button("Compare").clicked() {
+                                            compare_set = Some(*set_number);
+                                        }
+                                    });
+                                }
+                            }
+                        }
+                    });
+                });
+
+            if let Some(index) = selected_snapshot {
+                self.select_snapshot(index);
+            }
+            if let Some(set_number) = compare_set {
+                self.compare_with_snapshot(set_number);
+            }
+        }
+
+        // Only does anything once a file has been opened.
+        let snapshotted = self.snapshot_watcher.poll();
+        if self.history_window_open
+            && self
+                .gearset_path
+                .as_ref()
+                .is_some_and(|path| snapshotted.contains(path))
+        {
+            self.refresh_history();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        self.reload_if_changed();
+        ctx.request_repaint_after(Duration::from_secs(30));
+
+        let is_web = cfg!(target_arch = "wasm32");
+        if !is_web
+            && !self.export_window_open
+            && !self.import_window_open
+            && ctx.input_mut(|i| i.consume_shortcut(&OPEN_SHORTCUT))
+        {
+            self.show_open_dialog();
+        }
+
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            // The top panel is often a good place for a menu bar:
+
+            if self.export_window_open || self.import_window_open {
+                ui.disable();
+            }
+
+            egui::menu::bar(ui, |ui| {
+                // NOTE: no File->Quit on web pages!
+                if !is_web {
+                    ui.menu_button("File", |ui| {
+                        let open_button = egui::Button::new("Open")
+                            .shortcut_text(ui.ctx().format_shortcut(&OPEN_SHORTCUT));
+                        if ui.add(open_button).clicked() {
+                            self.show_open_dialog();
+                        }
+
+                        let mut open_recent = None;
+                        ui.add_enabled_ui(!self.persisted_data.recent_files.is_empty(), |ui| {
+                            ui.menu_button("Recent", |ui| {
+                                open_recent =
+                                    recent_files_menu(ui, &self.persisted_data.recent_files);
+                            });
+                        });
+                        if let Some(path) = open_recent {
+                            self.request_open_gearset_file(path, ctx);
+                        }
+
+                        if ui
+                            .add_enabled(self.unsaved_changes, egui::Button::new("Save"))
+                            .clicked()
+                        {
+                            self.save_gearsets(false);
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .add_enabled(self.gearset_path.is_some(), egui::Button::new("History"))
+                            .clicked()
+                        {
+                            self.open_history_window();
+                            ui.close_menu();
+                        }
+
+                        ui.add_enabled_ui(self.gearset_config.is_some(), |ui| {
+                            ui.menu_button("Report on all sets", |ui| {
+                                if let Some(target) = report_menu(ui) {
+                                    let gsc = self.gearset_config.as_ref().unwrap();
+                                    let report = get_report(
+                                        target,
+                                        &existing_gearsets(gsc),
+                                        self.data_provider.as_ref().unwrap(),
+                                    );
+                                    self.export_window_open = true;
+                                    self.export_window_target = target;
+                                    self.export_window_string = Some(report.into());
+                                }
+                            });
+
+                            // Only makes sense across the whole list, so it isn't a report format.
+                            if ui.button("Unused gear report").clicked() {
+                                let gsc = self.gearset_config.as_ref().unwrap();
+                                let report = get_gear_usage_report(
+                                    &existing_gearsets(gsc),
+                                    self.data_provider.as_ref().unwrap(),
+                                );
+                                self.export_window_open = true;
+                                self.export_window_target = ExportTarget::GearUsageReport;
+                                self.export_window_string = Some(report.into());
+                                ui.close_menu();
+                            }
+                        });
+
+                        if ui.button("Quit").clicked() {
+                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                        }
+                    });
+                    ui.add_space(16.0);
+                }
+
+                ui.selectable_value(&mut self.current_view, View::Gearsets, "Gearsets");
+                ui.selectable_value(&mut self.current_view, View::Macros, "Macros");
+                ui.selectable_value(&mut self.current_view, View::Hotbars, "Hotbars");
+                ui.selectable_value(&mut self.current_view, View::Keybinds, "Keybinds");
+                ui.selectable_value(&mut self.current_view, View::ItemOrder, "Item order");
+                ui.add_space(16.0);
+
+                if self.current_view == View::Gearsets && ui.button("Import").clicked() {
+                    self.import_window_open = true;
+                }
+                ui.add_space(16.0);
+
+                egui::widgets::global_theme_preference_buttons(ui);
+            });
+        });
+
+        let now = ctx.input(|i| i.time);
+        if self.status_message != self.shown_status_message {
+            self.shown_status_message = self.status_message.clone();
+            self.status_message_since = now;
+            ctx.request_repaint_after(Duration::from_secs_f64(STATUS_MESSAGE_SECONDS));
+        } else if self.status_message.is_some() {
+            let remaining = self.status_message_since + STATUS_MESSAGE_SECONDS - now;
+            if remaining <= 0.0 {
+                self.status_message = None;
+            } else {
+                ctx.request_repaint_after(Duration::from_secs_f64(remaining));
+            }
+        }
+
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if self.unsaved_changes {
+                    ui.label("Unsaved changes");
+                    ui.separator();
+                }
+                if self.changed_on_disk {
+                    ui.colored_label(ui.visuals().warn_fg_color, "Changed on disk");
+                    ui.separator();
+                }
+                if let Some(message) = &self.status_message {
+                    ui.label(message);
+                }
+            });
+        });
+
+        match self.current_view {
+            View::Gearsets => self.gearsets_view(ctx),
+            View::Macros => self.macros_view(ctx),
+            View::Hotbars => self.hotbars_view(ctx),
+            View::Keybinds => self.keybinds_view(ctx),
+            View::ItemOrder => self.item_order_view(ctx),
+        }
+
+        self.run_load_dialog(ctx);
+        self.run_save_dialog(ctx);
+    }
 }
@@ -0,0 +1,250 @@
+use crate::data_provider::DataProvider;
+use crate::model::{EquipSlot, PortableGearset, PortableItem};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlotStatus {
+    /// The slot has the target item.
+    Match,
+    /// The slot has the target item, but NQ when it could be HQ.
+    WrongQuality,
+    /// The slot has some other item in it.
+    WrongItem,
+    /// The slot is empty, but the target has an item there.
+    Missing,
+    /// The target doesn't have anything in this slot.
+    NotInTarget,
+}
+
+#[derive(Clone, Debug)]
+pub struct SlotConformance {
+    pub status: SlotStatus,
+    /// Materia on the target that aren't melded, as (type, grade).
+    pub missing_materia: Vec<(u16, u8)>,
+    /// Materia that are melded but aren't on the target, as (type, grade).
+    pub wrong_materia: Vec<(u16, u8)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Conformance {
+    pub slots: Vec<SlotConformance>,
+    /// How far along the set is towards the target, from 0 to 100.
+    pub percent: f32,
+}
+
+// xivgear and Etro only ever export NQ item ids, so the target's quality says nothing about
+// what's wanted. Crafted gear is best HQ, so only flag NQ pieces that could have been HQ.
+fn check_slot<T: DataProvider>(
+    target: &PortableItem,
+    actual: &PortableItem,
+    data_provider: &T,
+) -> SlotConformance {
+    if target.item_id.is_empty() {
+        return SlotConformance {
+            status: SlotStatus::NotInTarget,
+            missing_materia: Vec::new(),
+            wrong_materia: Vec::new(),
+        };
+    }
+
+    let status = if actual.item_id.is_empty() {
+        SlotStatus::Missing
+    } else if actual.item_id.base() != target.item_id.base() {
+        SlotStatus::WrongItem
+    } else if !actual.item_id.is_hq()
+        && data_provider
+            .get_item(actual.item_id.base())
+            .is_ok_and(|item| item.can_be_hq)
+    {
+        SlotStatus::WrongQuality
+    } else {
+        SlotStatus::Match
+    };
+
+    // Materia on the wrong item don't count towards anything.
+    let mut missing_materia = target.melds();
+    let mut wrong_materia = Vec::new();
+    if matches!(status, SlotStatus::Match | SlotStatus::WrongQuality) {
+        for materia in actual.melds() {
+            match missing_materia.iter().position(|m| *m == materia) {
+                Some(index) => {
+                    missing_materia.remove(index);
+                }
+                None => wrong_materia.push(materia),
+            }
+        }
+    }
+
+    SlotConformance {
+        status,
+        missing_materia,
+        wrong_materia,
+    }
+}
+
+/// Compares a gearset against a target (such as an imported BiS set), slot by slot.
+///
+/// The completion percentage gives one point for each target item that's equipped and one for
+/// each target materia that's melded on it. NQ versions of an item that could be HQ still get the
+/// item point, since the difference is usually small.
+pub fn check_conformance<T: DataProvider>(
+    target: &PortableGearset,
+    actual: &PortableGearset,
+    data_provider: &T,
+) -> Conformance {
+    let empty = PortableItem::default();
+    let mut actual_equipment: Vec<&PortableItem> = (0..target.equipment.len())
+        .map(|i| actual.equipment.get(i).unwrap_or(&empty))
+        .collect();
+
+    // Rings can go on either finger, so if the rings are the wrong way around, swap them.
+    let left_ring = EquipSlot::LeftRing.index();
+    let right_ring = EquipSlot::RightRing.index();
+    if actual_equipment.len() > right_ring {
+        let same = |a: &PortableItem, b: &PortableItem| a.item_id.base() == b.item_id.base();
+        let left = actual_equipment[left_ring];
+        let right = actual_equipment[right_ring];
+        if !same(left, &target.equipment[left_ring])
+            && same(left, &target.equipment[right_ring])
+            && same(right, &target.equipment[left_ring])
+        {
+            actual_equipment.swap(left_ring, right_ring);
+        }
+    }
+
+    let mut earned = 0;
+    let mut total = 0;
+    let slots: Vec<SlotConformance> = target
+        .equipment
+        .iter()
+        .zip(actual_equipment)
+        .map(|(target_item, actual_item)| {
+            let slot = check_slot(target_item, actual_item, data_provider);
+
+            if slot.status != SlotStatus::NotInTarget {
+                let target_materia = target_item.melds().len();
+                total += 1 + target_materia;
+                if matches!(slot.status, SlotStatus::Match | SlotStatus::WrongQuality) {
+                    earned += 1 + target_materia - slot.missing_materia.len();
+                }
+            }
+
+            slot
+        })
+        .collect();
+
+    Conformance {
+        slots,
+        percent: if total == 0 {
+            100.0
+        } else {
+            100.0 * earned as f32 / total as f32
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_provider::test_provider::TestProvider;
+
+    fn slot(conformance: &Conformance, slot: EquipSlot) -> &SlotConformance {
+        &conformance.slots[slot.index()]
+    }
+
+    #[test]
+    fn hq_crafted_piece_matches_nq_target() {
+        // Targets from xivgear and Etro never have HQ ids.
+        let target =
+            PortableGearset::with_items(&[(EquipSlot::Head, 43142, &[(20, 11), (20, 11)])]);
+        let actual =
+            PortableGearset::with_items(&[(EquipSlot::Head, 1_043_142, &[(20, 11), (20, 11)])]);
+
+        let conformance = check_conformance(&target, &actual, &TestProvider);
+        assert_eq!(
+            slot(&conformance, EquipSlot::Head).status,
+            SlotStatus::Match
+        );
+        assert_eq!(conformance.percent, 100.0);
+    }
+
+    #[test]
+    fn nq_crafted_piece_is_wrong_quality() {
+        let target = PortableGearset::with_items(&[(EquipSlot::Head, 43142, &[])]);
+        let actual = PortableGearset::with_items(&[(EquipSlot::Head, 43142, &[])]);
+
+        let conformance = check_conformance(&target, &actual, &TestProvider);
+        assert_eq!(
+            slot(&conformance, EquipSlot::Head).status,
+            SlotStatus::WrongQuality
+        );
+        // Still counts as having the item.
+        assert_eq!(conformance.percent, 100.0);
+    }
+
+    #[test]
+    fn nq_piece_that_cant_be_hq_matches() {
+        let target = PortableGearset::with_items(&[(EquipSlot::MainHand, 42958, &[])]);
+        let actual = PortableGearset::with_items(&[(EquipSlot::MainHand, 42958, &[])]);
+
+        let conformance = check_conformance(&target, &actual, &TestProvider);
+        assert_eq!(
+            slot(&conformance, EquipSlot::MainHand).status,
+            SlotStatus::Match
+        );
+    }
+
+    #[test]
+    fn swapped_rings_match() {
+        let target = PortableGearset::with_items(&[
+            (EquipSlot::LeftRing, 42001, &[]),
+            (EquipSlot::RightRing, 42002, &[]),
+        ]);
+        let actual = PortableGearset::with_items(&[
+            (EquipSlot::LeftRing, 42002, &[]),
+            (EquipSlot::RightRing, 42001, &[]),
+        ]);
+
+        let conformance = check_conformance(&target, &actual, &TestProvider);
+        assert_eq!(
+            slot(&conformance, EquipSlot::LeftRing).status,
+            SlotStatus::Match
+        );
+        assert_eq!(
+            slot(&conformance, EquipSlot::RightRing).status,
+            SlotStatus::Match
+        );
+    }
+
+    #[test]
+    fn missing_and_wrong_materia() {
+        let target =
+            PortableGearset::with_items(&[(EquipSlot::MainHand, 42958, &[(20, 11), (20, 11)])]);
+        let actual =
+            PortableGearset::with_items(&[(EquipSlot::MainHand, 42958, &[(20, 11), (21, 11)])]);
+
+        let conformance = check_conformance(&target, &actual, &TestProvider);
+        assert_eq!(
+            slot(&conformance, EquipSlot::MainHand).missing_materia,
+            vec![(20, 11)]
+        );
+        assert_eq!(
+            slot(&conformance, EquipSlot::MainHand).wrong_materia,
+            vec![(21, 11)]
+        );
+        // The item and one of its two materia.
+        assert_eq!(conformance.percent, 100.0 * 2.0 / 3.0);
+    }
+
+    #[test]
+    fn wrong_item_earns_nothing() {
+        let target = PortableGearset::with_items(&[(EquipSlot::MainHand, 42958, &[(20, 11)])]);
+        let actual = PortableGearset::with_items(&[(EquipSlot::MainHand, 42959, &[(20, 11)])]);
+
+        let conformance = check_conformance(&target, &actual, &TestProvider);
+        assert_eq!(
+            slot(&conformance, EquipSlot::MainHand).status,
+            SlotStatus::WrongItem
+        );
+        assert_eq!(conformance.percent, 0.0);
+    }
+}
@@ -1,9 +1,13 @@
 use egui::ImageSource;
+use image::RgbaImage;
 use std::error::Error;
 use std::fmt;
 
+use crate::model::Action;
 use crate::model::Item;
+use crate::model::ItemId;
 use crate::model::Materia;
+use crate::model::Stain;
 
 use image::error::ImageError;
 use image_dds::error::{CreateImageError, SurfaceError};
@@ -94,9 +98,127 @@ impl From<ImageError> for DataProviderError {
 }
 
 pub trait DataProvider {
-    fn get_item(&self, item_id: u32) -> Result<Item, DataProviderError>;
+    fn get_item(&self, item_id: ItemId) -> Result<Item, DataProviderError>;
     fn get_materia(&self, id: u32) -> Result<Materia, DataProviderError>;
+    /// Whether a class or job is part of a ClassJobCategory, such as the one an item needs.
+    fn class_job_category_includes(
+        &self,
+        category: u32,
+        class_job: u8,
+    ) -> Result<bool, DataProviderError>;
+    fn get_stain(&self, id: u32) -> Result<Stain, DataProviderError>;
+    fn get_class_job_category_name(&self, category: u32) -> Result<String, DataProviderError>;
+    fn get_action(&self, id: u32) -> Result<Action, DataProviderError>;
+    fn get_general_action(&self, id: u32) -> Result<Action, DataProviderError>;
+    fn get_emote(&self, id: u32) -> Result<Action, DataProviderError>;
+    fn get_craft_action(&self, id: u32) -> Result<Action, DataProviderError>;
+
+    /// Finds the materia type and grade that a materia item corresponds to.
+    fn find_materia_by_item_id(&self, item_id: u32) -> Result<(u32, u8), DataProviderError>;
 
     fn get_image(&self, path: &str) -> Result<ImageSource<'_>, DataProviderError>;
     fn get_ui_image_by_id(&self, id: u32) -> Result<ImageSource<'_>, DataProviderError>;
+    /// Like `get_image`, but decoded here for code that wants the pixels.
+    fn get_texture(&self, path: &str) -> Result<RgbaImage, DataProviderError>;
+    fn get_ui_texture_by_id(&self, id: u32) -> Result<RgbaImage, DataProviderError>;
+}
+
+/// A stand-in for the game data in tests, knowing just enough materia to resolve melds.
+#[cfg(test)]
+pub mod test_provider {
+    use super::*;
+
+    /// Materia type 20 is Savage Aim and 21 is Savage Might. Grade n is item 41750 + n, and
+    /// 41800 + n respectively.
+    ///
+    /// Every item is level 90 with two materia slots. Items 45000 to 45999 are unique rings and
+    /// the rest are body armour. Of those, 43000 to 43999 are crafted, so they can be HQ and be
+    /// overmelded.
+    pub struct TestProvider;
+
+    impl DataProvider for TestProvider {
+        fn get_item(&self, item_id: ItemId) -> Result<Item, DataProviderError> {
+            let crafted = (43000..44000).contains(&item_id.base_id());
+            let ring = (45000..46000).contains(&item_id.base_id());
+            Ok(Item {
+                id: item_id,
+                level_equip: 90,
+                equip_slot_category: if ring { 12 } else { 4 },
+                materia_slot_count: 2,
+                advanced_melds_permitted: crafted,
+                is_unique: ring,
+                can_be_hq: crafted,
+                ..Default::default()
+            })
+        }
+
+        fn get_materia(&self, id: u32) -> Result<Materia, DataProviderError> {
+            let first_item = match id {
+                20 => 41750,
+                21 => 41800,
+                _ => return Err(DataProviderError::ObjectNotFound("no such materia")),
+            };
+            Ok(Materia {
+                id,
+                item_id: (0..16).map(|grade| first_item + grade).collect(),
+                ..Default::default()
+            })
+        }
+
+        fn find_materia_by_item_id(&self, item_id: u32) -> Result<(u32, u8), DataProviderError> {
+            match item_id {
+                41750..=41765 => Ok((20, (item_id - 41750) as u8)),
+                41800..=41815 => Ok((21, (item_id - 41800) as u8)),
+                _ => Err(DataProviderError::ObjectNotFound("no materia for item")),
+            }
+        }
+
+        fn class_job_category_includes(
+            &self,
+            _category: u32,
+            _class_job: u8,
+        ) -> Result<bool, DataProviderError> {
+            Ok(true)
+        }
+
+        fn get_stain(&self, _id: u32) -> Result<Stain, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no stains in tests"))
+        }
+
+        fn get_class_job_category_name(&self, _category: u32) -> Result<String, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no categories in tests"))
+        }
+
+        fn get_action(&self, _id: u32) -> Result<Action, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no actions in tests"))
+        }
+
+        fn get_general_action(&self, _id: u32) -> Result<Action, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no actions in tests"))
+        }
+
+        fn get_emote(&self, _id: u32) -> Result<Action, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no emotes in tests"))
+        }
+
+        fn get_craft_action(&self, _id: u32) -> Result<Action, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no actions in tests"))
+        }
+
+        fn get_image(&self, _path: &str) -> Result<ImageSource<'_>, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no images in tests"))
+        }
+
+        fn get_ui_image_by_id(&self, _id: u32) -> Result<ImageSource<'_>, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no images in tests"))
+        }
+
+        fn get_texture(&self, _path: &str) -> Result<RgbaImage, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no images in tests"))
+        }
+
+        fn get_ui_texture_by_id(&self, _id: u32) -> Result<RgbaImage, DataProviderError> {
+            Err(DataProviderError::ObjectNotFound("no images in tests"))
+        }
+    }
 }
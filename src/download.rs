@@ -0,0 +1,36 @@
+use eframe::wasm_bindgen::JsCast as _;
+
+fn percent_encode(contents: &str) -> String {
+    let mut encoded = String::with_capacity(contents.len() * 3);
+    for byte in contents.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Has the browser download `contents` as a file, by clicking a temporary `data:` link.
+pub fn download_text(file_name: &str, contents: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        log::error!("no document to download {} from", file_name);
+        return;
+    };
+
+    let Ok(anchor) = document.create_element("a") else {
+        return;
+    };
+    let href = format!("data:text/plain;charset=utf-8,{}", percent_encode(contents));
+    if anchor.set_attribute("href", &href).is_err()
+        || anchor.set_attribute("download", file_name).is_err()
+    {
+        return;
+    }
+
+    if let Ok(anchor) = anchor.dyn_into::<web_sys::HtmlElement>() {
+        anchor.click();
+    }
+}
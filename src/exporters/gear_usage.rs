@@ -0,0 +1,111 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::model::{class_job_abbreviation, promote_to_job, EquipSlot, ItemId};
+use libxivdat::xiv_gearset::Gearset;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+fn set_label(gearset: &Gearset) -> String {
+    format!(
+        "#{} {} ({})",
+        gearset.set_number,
+        gearset.name,
+        class_job_abbreviation(gearset.class_job)
+    )
+}
+
+fn item_name<T: DataProvider>(data_provider: &T, item_id: ItemId) -> String {
+    data_provider
+        .get_item(item_id)
+        .map(|item| item.name)
+        .unwrap_or_else(|_| format!("Unknown item {}", item_id))
+}
+
+/// Renders a plain-text report of items worth a second look before discarding anything: items
+/// only used by obsolete sets, items shared between jobs, and items used as both HQ and NQ.
+///
+/// A set counts as obsolete when another set for the same job has a strictly higher average item
+/// level, so sets tied for the best are all kept.
+pub fn get_gear_usage_report<T: DataProvider>(gearsets: &[&Gearset], data_provider: &T) -> String {
+    // The best average item level for each job; sets below it are obsolete.
+    let mut best_item_level = HashMap::new();
+    for gearset in gearsets.iter().copied() {
+        let best = best_item_level
+            .entry(promote_to_job(gearset.class_job))
+            .or_insert(gearset.average_item_level);
+        if gearset.average_item_level > *best {
+            *best = gearset.average_item_level;
+        }
+    }
+    let is_obsolete = |gearset: &Gearset| {
+        best_item_level
+            .get(&promote_to_job(gearset.class_job))
+            .is_some_and(|best| gearset.average_item_level < *best)
+    };
+
+    // Which sets use each item, and in which qualities.
+    let mut users: BTreeMap<ItemId, Vec<&Gearset>> = BTreeMap::new();
+    let mut qualities: BTreeMap<ItemId, BTreeSet<bool>> = BTreeMap::new();
+    for gearset in gearsets.iter().copied() {
+        for (slot, eq) in EquipSlot::ALL.iter().zip(gearset.equipment.iter()) {
+            // Soul crystals can't be discarded or shared, so they'd only add noise.
+            let item_id = ItemId(eq.item_id);
+            if *slot == EquipSlot::SoulCrystal || item_id.is_empty() {
+                continue;
+            }
+
+            let sets = users.entry(item_id.base()).or_default();
+            if !sets.iter().any(|gs| gs.set_number == gearset.set_number) {
+                sets.push(gearset);
+            }
+            qualities
+                .entry(item_id.base())
+                .or_default()
+                .insert(item_id.is_hq());
+        }
+    }
+
+    let mut out = String::from("Only used by obsolete sets\n");
+    for (item_id, sets) in users.iter() {
+        if sets.iter().all(|gs| is_obsolete(gs)) {
+            out.push_str(&format!("  {}\n", item_name(data_provider, *item_id)));
+            for gearset in sets {
+                out.push_str(&format!("    {}\n", set_label(gearset)));
+            }
+        }
+    }
+
+    out.push_str("\nShared between jobs\n");
+    for (item_id, sets) in users.iter() {
+        let jobs: BTreeSet<u8> = sets.iter().map(|gs| promote_to_job(gs.class_job)).collect();
+        if jobs.len() > 1 {
+            let jobs: Vec<&str> = jobs.into_iter().map(class_job_abbreviation).collect();
+            out.push_str(&format!(
+                "  {}: {}\n",
+                item_name(data_provider, *item_id),
+                jobs.join(", ")
+            ));
+        }
+    }
+
+    out.push_str("\nUsed as both HQ and NQ\n");
+    for (item_id, quality) in qualities.iter() {
+        if quality.len() > 1 {
+            out.push_str(&format!("  {}\n", item_name(data_provider, *item_id)));
+            for gearset in users[item_id].iter() {
+                let hq = gearset
+                    .equipment
+                    .iter()
+                    .map(|eq| ItemId(eq.item_id))
+                    .any(|id| id.base() == *item_id && id.is_hq());
+                out.push_str(&format!(
+                    "    {} ({})\n",
+                    set_label(gearset),
+                    if hq { "HQ" } else { "NQ" }
+                ));
+            }
+        }
+    }
+
+    out
+}
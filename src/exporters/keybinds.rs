@@ -0,0 +1,36 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::keybind_file::Keybind;
+use serde_json::{Map, Value};
+
+//
+// [{"command":"MOVE_FORE",
+//   "primary":"57.0",
+//   "secondary":"26.0"},
+//  ...]
+//
+
+/// Renders keybinds as JSON that `parse_keybind_json` can read back, bindings left as stored.
+pub fn get_keybind_json(keybinds: &[Keybind]) -> String {
+    let entries = keybinds
+        .iter()
+        .map(|keybind| {
+            let mut entry = Map::new();
+            entry.insert(
+                "command".to_string(),
+                Value::String(keybind.command.clone()),
+            );
+            entry.insert(
+                "primary".to_string(),
+                Value::String(keybind.primary.clone()),
+            );
+            entry.insert(
+                "secondary".to_string(),
+                Value::String(keybind.secondary.clone()),
+            );
+            Value::Object(entry)
+        })
+        .collect();
+
+    Value::Array(entries).to_string()
+}
@@ -0,0 +1,63 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use libxivdat::xiv_macro::Macro;
+use serde_json::{Map, Value};
+
+/// Whether a macro slot has anything in it.
+pub fn macro_is_empty(m: &Macro) -> bool {
+    m.title.is_empty() && m.lines.iter().all(|line| line.is_empty())
+}
+
+/// Renders every non-empty macro in a book as plain text, ready to copy back in line by line.
+pub fn get_macro_text(macros: &[Macro]) -> String {
+    let mut out = String::new();
+
+    for (i, m) in macros.iter().enumerate() {
+        if macro_is_empty(m) {
+            continue;
+        }
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("# {}: {}\n", i, m.title));
+        for line in m.lines.iter() {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+//
+// [{"index":0,
+//   "title":"Opener",
+//   "iconId":"0B1",
+//   "iconKey":"00101D1",
+//   "lines":["/ac \"Reflect\" <wait.2>", ...]},
+//  ...]
+//
+
+/// Renders every non-empty macro in a book as JSON that `parse_macro_json` can read back.
+pub fn get_macro_json(macros: &[Macro]) -> String {
+    let entries = macros
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| !macro_is_empty(m))
+        .map(|(i, m)| {
+            let mut entry = Map::new();
+            entry.insert("index".to_string(), Value::Number(i.into()));
+            entry.insert("title".to_string(), Value::String(m.title.clone()));
+            entry.insert("iconId".to_string(), Value::String(m.icon_id.clone()));
+            entry.insert("iconKey".to_string(), Value::String(m.icon_key.clone()));
+            entry.insert(
+                "lines".to_string(),
+                Value::Array(m.lines.iter().cloned().map(Value::String).collect()),
+            );
+            Value::Object(entry)
+        })
+        .collect();
+
+    Value::Array(entries).to_string()
+}
@@ -1,5 +1,92 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+mod gear_usage;
+mod keybinds;
+mod macros;
+mod report;
+mod share_code;
+mod teamcraft;
 mod xivgear;
 
+pub use gear_usage::get_gear_usage_report;
+pub use keybinds::get_keybind_json;
+pub use macros::{get_macro_json, get_macro_text, macro_is_empty};
+pub use report::{get_csv_report, get_markdown_report, get_text_report};
+pub use share_code::{get_share_code, SHARE_CODE_PREFIX};
+pub use teamcraft::get_teamcraft_json;
 pub use xivgear::get_xivgear_json;
+
+/// Where an export is meant to be pasted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExportTarget {
+    Xivgear,
+    Teamcraft,
+    ShareCode,
+    TextReport,
+    MarkdownReport,
+    CsvReport,
+    GearUsageReport,
+    MacroText,
+    MacroJson,
+    KeybindJson,
+}
+
+impl ExportTarget {
+    /// The name and URL of the page that accepts this export, if it belongs to a website.
+    pub fn import_page(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            ExportTarget::Xivgear => Some((
+                "xivgear's import page",
+                "https://xivgear.app/?page=importsheet",
+            )),
+            ExportTarget::Teamcraft => Some((
+                "Teamcraft's gearsets page",
+                "https://ffxivteamcraft.com/gearsets",
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn is_json(&self) -> bool {
+        matches!(
+            self,
+            ExportTarget::Xivgear
+                | ExportTarget::Teamcraft
+                | ExportTarget::MacroJson
+                | ExportTarget::KeybindJson
+        )
+    }
+
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            ExportTarget::Xivgear => "xivgear.json",
+            ExportTarget::Teamcraft => "teamcraft.json",
+            ExportTarget::ShareCode => "sharecode.txt",
+            ExportTarget::TextReport => "report.txt",
+            ExportTarget::MarkdownReport => "report.md",
+            ExportTarget::CsvReport => "report.csv",
+            ExportTarget::GearUsageReport => "gear-usage.txt",
+            ExportTarget::MacroText => "macros.txt",
+            ExportTarget::MacroJson => "macros.json",
+            ExportTarget::KeybindJson => "keybinds.json",
+        }
+    }
+
+    /// What to tell the user to do with an export that doesn't go to a website.
+    pub fn instructions(&self) -> &'static str {
+        match self {
+            ExportTarget::ShareCode => {
+                "Share the following code, or paste it into roarich's import window."
+            }
+            ExportTarget::MarkdownReport => "Paste the following into a wiki page or Discord.",
+            ExportTarget::CsvReport => "Paste the following into a spreadsheet.",
+            ExportTarget::MacroJson => {
+                "Share the following JSON, or import it into another macro book."
+            }
+            ExportTarget::KeybindJson => {
+                "Save the following JSON, then import it on another character or machine."
+            }
+            _ => "Copy the following text.",
+        }
+    }
+}
@@ -0,0 +1,186 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::model::base_param::base_param_display_name;
+use crate::model::{class_job_abbreviation, EquipSlot, ItemId, EQUIPMENT_SLOT_COUNT};
+use libxivdat::xiv_gearset::Gearset;
+
+struct ReportRow {
+    slot: &'static str,
+    item_id: ItemId,
+    item_name: String,
+    item_level: u16,
+    materia: Vec<String>,
+}
+
+fn report_title(gearset: &Gearset) -> String {
+    format!(
+        "#{} {} ({}, i{})",
+        gearset.set_number,
+        gearset.name,
+        class_job_abbreviation(gearset.class_job),
+        gearset.average_item_level
+    )
+}
+
+fn report_rows<T: DataProvider>(gearset: &Gearset, data_provider: &T) -> Vec<ReportRow> {
+    let mut rows = Vec::with_capacity(EQUIPMENT_SLOT_COUNT);
+
+    for (slot, eq) in EquipSlot::ALL.iter().zip(gearset.equipment.iter()) {
+        let item_id = ItemId(eq.item_id);
+        if item_id.is_empty() {
+            continue;
+        }
+
+        let (item_name, item_level) = match data_provider.get_item(item_id) {
+            Ok(item) => (item.name, item.level_item),
+            Err(_) => (format!("Unknown item {}", item_id), 0),
+        };
+
+        let mut materia = Vec::with_capacity(5);
+        for m in 0..eq.materia_types.len() {
+            if eq.materia_types[m] == 0 {
+                continue;
+            }
+
+            let Ok(materia_info) = data_provider.get_materia(eq.materia_types[m] as u32) else {
+                continue;
+            };
+            let grade = eq.materia_grades[m] as usize;
+            let materia_item_id = materia_info.item_id.get(grade).copied().unwrap_or(0);
+
+            if materia_item_id != 0 {
+                if let Ok(materia_item) = data_provider.get_item(ItemId(materia_item_id)) {
+                    materia.push(materia_item.name);
+                }
+            } else {
+                // Relic stat bonuses are stored like materia, but don't have an item.
+                materia.push(format!(
+                    "+{} {}",
+                    materia_info
+                        .base_param_value
+                        .get(grade)
+                        .copied()
+                        .unwrap_or(0),
+                    base_param_display_name(materia_info.base_param_id)
+                ));
+            }
+        }
+
+        rows.push(ReportRow {
+            slot: slot.display_name(),
+            item_id,
+            item_name,
+            item_level,
+            materia,
+        });
+    }
+
+    rows
+}
+
+/// Renders gearsets as plain-text tables, padded out so they line up in a monospaced font.
+pub fn get_text_report<T: DataProvider>(gearsets: &[&Gearset], data_provider: &T) -> String {
+    let mut out = String::new();
+
+    for gearset in gearsets {
+        let rows = report_rows(gearset, data_provider);
+
+        let slot_width = rows.iter().map(|r| r.slot.len()).max().unwrap_or(0).max(4);
+        let item_width = rows
+            .iter()
+            .map(|r| r.item_name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&report_title(gearset));
+        out.push('\n');
+        out.push_str(&format!(
+            "{:slot_width$}  {:item_width$}  {:>4}  Materia\n",
+            "Slot", "Item", "iLvl"
+        ));
+        for row in rows {
+            out.push_str(
+                format!(
+                    "{:slot_width$}  {:item_width$}  {:>4}  {}",
+                    row.slot,
+                    row.item_name,
+                    row.item_level,
+                    row.materia.join(", ")
+                )
+                .trim_end(),
+            );
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Renders gearsets as Markdown tables, one per set.
+pub fn get_markdown_report<T: DataProvider>(gearsets: &[&Gearset], data_provider: &T) -> String {
+    let mut out = String::new();
+
+    for gearset in gearsets {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "### {}\n\n",
+            markdown_escape(&report_title(gearset))
+        ));
+        out.push_str("| Slot | Item | iLvl | Materia |\n");
+        out.push_str("| --- | --- | ---: | --- |\n");
+
+        for row in report_rows(gearset, data_provider) {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                row.slot,
+                markdown_escape(&row.item_name),
+                row.item_level,
+                markdown_escape(&row.materia.join(", "))
+            ));
+        }
+    }
+
+    out
+}
+
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Renders gearsets as CSV, with one row per equipped item.
+pub fn get_csv_report<T: DataProvider>(gearsets: &[&Gearset], data_provider: &T) -> String {
+    let mut out = String::from("set_number,set_name,job,slot,item_id,item,item_level,materia\n");
+
+    for gearset in gearsets {
+        for row in report_rows(gearset, data_provider) {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                gearset.set_number,
+                csv_escape(&gearset.name),
+                class_job_abbreviation(gearset.class_job),
+                row.slot,
+                row.item_id,
+                csv_escape(&row.item_name),
+                row.item_level,
+                csv_escape(&row.materia.join("; "))
+            ));
+        }
+    }
+
+    out
+}
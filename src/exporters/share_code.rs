@@ -0,0 +1,65 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::model::PortableGearset;
+
+pub const SHARE_CODE_PREFIX: &str = "rch1";
+
+//
+// rch1.<job>.<slot 0>.<slot 1>. ... .<slot 13>
+//
+// Every number is base36 to keep things short. Each slot is a comma-separated list of the item
+// id (HQ items keep their offset) followed by materia type/grade pairs, with trailing
+// empty materia dropped. An empty slot is an empty string. For example:
+//
+//  rch1.v.wvy,k,b,k,b.yhd,k,b,j,b.ymi,j,b,j,b. ... ..
+//
+// There are no characters in this that Discord will try to turn into formatting.
+//
+
+fn to_base36(mut value: u32) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    if value == 0 {
+        return "0".to_string();
+    }
+
+    let mut digits = Vec::new();
+    while value > 0 {
+        digits.push(DIGITS[(value % 36) as usize]);
+        value /= 36;
+    }
+    digits.reverse();
+
+    String::from_utf8(digits).unwrap()
+}
+
+pub fn get_share_code(gearset: &PortableGearset) -> std::string::String {
+    let mut fields = Vec::with_capacity(16);
+    fields.push(SHARE_CODE_PREFIX.to_string());
+    fields.push(to_base36(gearset.class_job as u32));
+
+    for eq in gearset.equipment.iter() {
+        if eq.item_id.is_empty() {
+            fields.push(String::new());
+            continue;
+        }
+
+        let mut slot = vec![to_base36(eq.item_id.0)];
+
+        let materia_count = eq
+            .materia_types
+            .iter()
+            .rposition(|t| *t != 0)
+            .map_or(0, |last| last + 1);
+        for m in 0..materia_count {
+            slot.push(to_base36(eq.materia_types[m] as u32));
+            slot.push(to_base36(
+                eq.materia_grades.get(m).copied().unwrap_or(0) as u32
+            ));
+        }
+
+        fields.push(slot.join(","));
+    }
+
+    fields.join(".")
+}
@@ -0,0 +1,91 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::model::{EquipSlot, ItemId};
+use libxivdat::xiv_gearset::Gearset;
+use serde_json::{Map, Value};
+
+//
+// {"name":"Carpenter",
+//  "job":8,
+//  "mainHand":{"itemId":43733,"hq":true,"materias":[41779,41780],"materiaSlots":2,"canOvermeld":true},
+//  "offHand":{"itemId":43734,"hq":true,"materias":[41779,0],"materiaSlots":2,"canOvermeld":true},
+//  ...
+//  "crystal":{"itemId":4000,"hq":false,"materias":[],"materiaSlots":0,"canOvermeld":false}}
+//
+// Teamcraft's job is the raw ClassJob id, so unlike xivgear we can keep base classes as-is
+// instead of promoting them to their job.
+//
+
+pub fn get_teamcraft_json<T: DataProvider>(
+    gearset: &Gearset,
+    data_provider: &T,
+) -> std::string::String {
+    let mut root_map = Map::new();
+    root_map.insert("name".to_string(), Value::String(gearset.name.clone()));
+    root_map.insert("job".to_string(), Value::Number(gearset.class_job.into()));
+
+    for (slot, eq) in EquipSlot::ALL.iter().zip(gearset.equipment.iter()) {
+        // Teamcraft keeps the belt key around, but there's no reason to emit it for an
+        // empty slot.
+        let item_id = ItemId(eq.item_id);
+        if item_id.is_empty() {
+            continue;
+        }
+
+        let mut item_entry = Map::new();
+        // Unlike xivgear, Teamcraft cares about HQ because crafting and gathering stats
+        // differ a lot between NQ and HQ.
+        item_entry.insert(
+            "itemId".to_string(),
+            Value::Number(item_id.base_id().into()),
+        );
+        item_entry.insert("hq".to_string(), Value::Bool(item_id.is_hq()));
+
+        // Teamcraft wants materia item ids, with 0 for an empty slot.
+        let mut materia_vec = Vec::with_capacity(5);
+        for m in 0..eq.materia_types.len() {
+            let mut materia_item_id = 0;
+            if eq.materia_types[m] != 0 {
+                if let Ok(materia_info) = data_provider.get_materia(eq.materia_types[m] as u32) {
+                    materia_item_id = materia_info
+                        .item_id
+                        .get(eq.materia_grades[m] as usize)
+                        .copied()
+                        .unwrap_or(0);
+                }
+            }
+            materia_vec.push(Value::Number(materia_item_id.into()));
+        }
+
+        // Teamcraft decides how many materia it can meld (and whether to allow overmelds) from
+        // these, so fill them in from the item data.
+        let (materia_slots, can_overmeld) = match data_provider.get_item(item_id.base()) {
+            Ok(item) => (item.materia_slot_count, item.advanced_melds_permitted),
+            Err(_) => (0, false),
+        };
+
+        // Trim trailing empty slots past what the item can take, so we don't claim more slots
+        // than the item has.
+        while materia_vec.len() > materia_slots as usize
+            && materia_vec.last() == Some(&Value::Number(0.into()))
+        {
+            materia_vec.pop();
+        }
+        item_entry.insert("materias".to_string(), Value::Array(materia_vec));
+        item_entry.insert(
+            "materiaSlots".to_string(),
+            Value::Number(materia_slots.into()),
+        );
+        item_entry.insert("canOvermeld".to_string(), Value::Bool(can_overmeld));
+
+        root_map.insert(
+            slot.teamcraft_label().to_string(),
+            Value::Object(item_entry),
+        );
+    }
+
+    let root = Value::Object(root_map);
+
+    root.to_string()
+}
@@ -2,33 +2,10 @@
 
 //use std::collections::HashMap;
 use crate::data_provider::DataProvider;
+use crate::model::{class_job_abbreviation, promote_to_job, EquipSlot, ItemId};
 use libxivdat::xiv_gearset::Gearset;
 use serde_json::{Map, Value};
 
-const XIVGEAR_ITEM_LABELS: [&str; 14] = [
-    "Weapon",
-    "OffHand",
-    "Head",
-    "Body",
-    "Hand",
-    "",
-    "Legs",
-    "Feet",
-    "Ears",
-    "Neck",
-    "Wrist",
-    "RingLeft",
-    "RingRight",
-    "SoulCrystal",
-];
-
-const CLASSJOB_NAMES: [&str; 43] = [
-    "ADV", "GLA", "PGL", "MRD", "LNC", "ARC", "CNJ", "THM", "CRP", "BSM", "ARM", "GSM", "LTW",
-    "WVR", "ALC", "CUL", "MIN", "BTN", "FSH", "PLD", "MNK", "WAR", "DRG", "BRD", "WHM", "BLM",
-    "ACN", "SMN", "SCH", "ROG", "NIN", "MCH", "DRK", "AST", "SAM", "RDM", "BLU", "GNB", "DNC",
-    "RPR", "SGE", "VPR", "PCT",
-];
-
 fn base_param_name(param: i32) -> &'static str {
     match param {
         6 => "piety",
@@ -60,58 +37,24 @@ fn base_param_name(param: i32) -> &'static str {
 //  "job":"MCH"}
 //
 
-// xivgear doesn't understand base classes, only jobs.
-//
-// this information doesn't seem to be in the ClassJob table anywhere, but thankfully FFXIV will
-// never get a job with a different base class ever again, so hardcoding this table is fine.
-//
-// (xivgear also doesn't seem to keep track of any items that are less than ilvl 290 (lowest lv70 gear),
-// so exporting a gearset that isn't on a class with a job is probably of limited usefulness anyway...)
-fn promote_to_job(class_job: u8) -> u8 {
-    match class_job {
-        // GLA -> PLD
-        1 => 19,
-        // PGL -> MNK
-        2 => 20,
-        // MRD -> WAR
-        3 => 21,
-        // LNC -> DRG
-        4 => 22,
-        // ARC -> BRD
-        5 => 23,
-        // CNJ -> WHM
-        6 => 24,
-        // THM -> BLM
-        7 => 25,
-        // ACN -> SMN
-        26 => 27,
-        // all others
-        x => x,
-    }
-}
-
 pub fn get_xivgear_json<T: DataProvider>(
     gearset: &Gearset,
     data_provider: &T,
 ) -> std::string::String {
     let mut items_map = Map::new();
 
-    for (i, eq) in gearset.equipment.iter().enumerate() {
+    for (slot, eq) in EquipSlot::ALL.iter().zip(gearset.equipment.iter()) {
         // Skip belt slot and soul crystal
-        if i == 5 || i == 13 {
+        let Some(label) = slot.xivgear_label() else {
             continue;
-        }
+        };
 
         // xivgear doesn't seem to care if we put down an offhand item for a job that
         // doesn't use it
 
         let mut item_entry = Map::new();
         // xivgear assumes that all HQ-able gear is HQ.
-        let item_id = if eq.item_id > 1000000 {
-            eq.item_id - 1000000
-        } else {
-            eq.item_id
-        };
+        let item_id = ItemId(eq.item_id).base_id();
         item_entry.insert("id".to_string(), Value::Number(item_id.into()));
 
         // We have to resolve materia (which is stored as class+grade) to the item id
@@ -145,10 +88,7 @@ pub fn get_xivgear_json<T: DataProvider>(
             item_entry.insert("relicStats".to_string(), Value::Object(relic_stats));
         }
 
-        items_map.insert(
-            XIVGEAR_ITEM_LABELS[i].to_string(),
-            Value::Object(item_entry),
-        );
+        items_map.insert(label.to_string(), Value::Object(item_entry));
     }
 
     let mut root_map = Map::new();
@@ -156,9 +96,13 @@ pub fn get_xivgear_json<T: DataProvider>(
     // TODO: Should we look at item equip levels to figure out if this is 70/80/90/100?
     // (xivgear doesn't support anything lower)
     root_map.insert("level".to_string(), Value::Number(100.into()));
+    // xivgear doesn't understand base classes, only jobs.
+    //
+    // (xivgear also doesn't seem to keep track of any items that are less than ilvl 290 (lowest lv70 gear),
+    // so exporting a gearset that isn't on a class with a job is probably of limited usefulness anyway...)
     root_map.insert(
         "job".to_string(),
-        Value::String(CLASSJOB_NAMES[promote_to_job(gearset.class_job) as usize].to_string()),
+        Value::String(class_job_abbreviation(promote_to_job(gearset.class_job)).to_string()),
     );
     root_map.insert("items".to_string(), Value::Object(items_map));
 
@@ -0,0 +1,92 @@
+use notify::{Config, Event, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+fn event_handler(
+    file_name: OsString,
+    tx: Sender<()>,
+    ctx: egui::Context,
+) -> impl FnMut(notify::Result<Event>) + Send + 'static {
+    move |event| {
+        let Ok(event) = event else {
+            return;
+        };
+        if event.kind.is_access() {
+            return;
+        }
+        if event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == Some(file_name.as_os_str()))
+        {
+            let _ = tx.send(());
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Watches a single file for changes.
+///
+/// This uses the platform's native file notifications (inotify on Linux) where possible, and
+/// falls back to polling when those aren't available, such as on some network drives.
+pub struct FileWatcher {
+    path: PathBuf,
+    // Never read, but events stop once it's dropped.
+    _watcher: Box<dyn Watcher>,
+    rx: Receiver<()>,
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path, ctx: &egui::Context) -> notify::Result<FileWatcher> {
+        // The game may replace the file rather than rewrite it, which would leave a watch on
+        // the file itself pointing at nothing, so watch the folder it's in instead.
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let file_name = path.file_name().unwrap_or_default().to_os_string();
+        let (tx, rx) = channel();
+
+        let native_watcher =
+            notify::recommended_watcher(event_handler(file_name.clone(), tx.clone(), ctx.clone()))
+                .and_then(|mut watcher: RecommendedWatcher| {
+                    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+                    Ok(watcher)
+                });
+
+        let watcher: Box<dyn Watcher> = match native_watcher {
+            Ok(watcher) => Box::new(watcher),
+            Err(err) => {
+                log::info!(
+                    "native file watching unavailable ({}), polling {:?}",
+                    err,
+                    path
+                );
+                let mut watcher = PollWatcher::new(
+                    event_handler(file_name, tx, ctx.clone()),
+                    Config::default().with_poll_interval(POLL_INTERVAL),
+                )?;
+                watcher.watch(dir, RecursiveMode::NonRecursive)?;
+                Box::new(watcher)
+            }
+        };
+
+        Ok(FileWatcher {
+            path: path.to_path_buf(),
+            _watcher: watcher,
+            rx,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file has changed since this was last called.
+    pub fn has_changed(&self) -> bool {
+        // Saving tends to produce a burst of events; they all count as one change.
+        self.rx.try_iter().count() > 0
+    }
+}
@@ -0,0 +1,183 @@
+use libxivdat::dat_error::DATError;
+use libxivdat::dat_file::DATFile;
+use libxivdat::xiv_gearset::{read_gearset, write_gearset, GearsetList};
+
+use crate::timestamp::file_timestamp;
+
+use std::error::Error;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// GEARSET.DAT stores names as a fixed 48-byte null-terminated field.
+const MAX_GEARSET_NAME_BYTES: usize = 47;
+
+#[derive(Debug)]
+pub enum GearsetFileError {
+    Io(std::io::Error),
+    Dat(DATError),
+    /// Writing out what was read gives back different bytes, so saving could lose data.
+    RoundTripMismatch,
+}
+
+impl fmt::Display for GearsetFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GearsetFileError::Io(err) => write!(f, "{}", err),
+            GearsetFileError::Dat(err) => write!(f, "{}", err),
+            GearsetFileError::RoundTripMismatch => write!(
+                f,
+                "this version of the file isn't understood well enough to save it safely"
+            ),
+        }
+    }
+}
+
+impl Error for GearsetFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GearsetFileError::Io(err) => Some(err),
+            GearsetFileError::Dat(err) => Some(err),
+            GearsetFileError::RoundTripMismatch => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GearsetFileError {
+    fn from(err: std::io::Error) -> Self {
+        GearsetFileError::Io(err)
+    }
+}
+
+impl From<DATError> for GearsetFileError {
+    fn from(err: DATError) -> Self {
+        GearsetFileError::Dat(err)
+    }
+}
+
+/// Reads a GEARSET.DAT (or a snapshot of one).
+pub fn load_gearsets(path: &Path) -> Result<GearsetList, GearsetFileError> {
+    let mut dat_file = DATFile::open(path)?;
+    Ok(read_gearset(&mut dat_file)?)
+}
+
+/// Checks that writing a file's gearsets straight back out reproduces it byte for byte, using a
+/// scratch copy so the file itself isn't touched.
+pub fn check_round_trip(path: &Path) -> Result<(), GearsetFileError> {
+    let original = std::fs::read(path)?;
+    let gearsets = load_gearsets(path)?;
+
+    let scratch_path = std::env::temp_dir().join(format!(
+        "roarich-{}-{}",
+        std::process::id(),
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    std::fs::write(&scratch_path, &original)?;
+    let rewritten = write_gearsets(&scratch_path, &gearsets)
+        .and_then(|()| std::fs::read(&scratch_path).map_err(GearsetFileError::from));
+    // Clean up whether or not the write worked.
+    let _ = std::fs::remove_file(&scratch_path);
+
+    if rewritten? != original {
+        return Err(GearsetFileError::RoundTripMismatch);
+    }
+
+    Ok(())
+}
+
+fn write_gearsets(path: &Path, gearsets: &GearsetList) -> Result<(), GearsetFileError> {
+    let mut dat_file = DATFile::open_options(path, OpenOptions::new().read(true).write(true))?;
+    write_gearset(&mut dat_file, gearsets)?;
+    Ok(())
+}
+
+/// Copies `path` to a timestamped file next to it, returning where the copy went.
+pub fn backup_file(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let backup_path = path.with_file_name(format!(
+        "{}.{}.bak",
+        file_name,
+        file_timestamp(SystemTime::now())
+    ));
+
+    std::fs::copy(path, &backup_path)?;
+
+    Ok(backup_path)
+}
+
+/// Writes a gearset list back over the DAT file it came from, after backing the original up.
+///
+/// libxivdat takes care of the header, the XOR mask and the checksum; we only hand it the
+/// gearsets. Nothing is written unless the file on disk survives a round trip unchanged, since
+/// anything libxivdat doesn't understand would otherwise be lost. Returns the path of the backup.
+pub fn save_gearsets(path: &Path, gearsets: &GearsetList) -> Result<PathBuf, GearsetFileError> {
+    check_round_trip(path)?;
+    let backup_path = backup_file(path)?;
+    write_gearsets(path, gearsets)?;
+
+    Ok(backup_path)
+}
+
+/// Shortens a name so that it fits in GEARSET.DAT, without splitting a character.
+pub fn truncate_gearset_name(name: &str) -> String {
+    let mut end = name.len().min(MAX_GEARSET_NAME_BYTES);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    name[..end].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EquipSlot, PortableGearset};
+    use libxivdat::dat_type::DATType;
+    use libxivdat::xiv_gearset::GearsetFlags;
+
+    #[test]
+    fn written_gearsets_round_trip() {
+        let path = std::env::temp_dir().join(format!("roarich-{}-GEARSET.DAT", std::process::id()));
+        DATFile::create(&path, DATType::GEARSET).unwrap();
+
+        let mut gearsets = load_gearsets(&path).unwrap();
+        let gearset = &mut gearsets.gearsets[3];
+        gearset.name = "Test".to_string();
+        gearset.class_job = 31;
+        gearset.glamour_plate = 2;
+        gearset.flags.insert(GearsetFlags::Exists);
+        gearset.equipment[EquipSlot::Head.index()].item_id = 43142;
+        gearsets.current = 3;
+
+        let read_back = write_gearsets(&path, &gearsets)
+            .and_then(|()| check_round_trip(&path))
+            .and_then(|()| load_gearsets(&path));
+        // Clean up whether or not the test passes.
+        let _ = std::fs::remove_file(&path);
+        let read_back = read_back.unwrap();
+
+        assert_eq!(read_back.current, gearsets.current);
+        assert_eq!(read_back.gearsets.len(), gearsets.gearsets.len());
+        for (actual, expected) in read_back.gearsets.iter().zip(gearsets.gearsets.iter()) {
+            assert_eq!(actual.set_number, expected.set_number);
+            assert_eq!(actual.flags, expected.flags);
+            assert_eq!(actual.glamour_plate, expected.glamour_plate);
+            assert_eq!(
+                PortableGearset::from(actual),
+                PortableGearset::from(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        let name = "é".repeat(30);
+        let truncated = truncate_gearset_name(&name);
+        assert_eq!(truncated.len(), 46);
+        assert!(name.starts_with(&truncated));
+        assert_eq!(truncate_gearset_name("Short"), "Short");
+    }
+}
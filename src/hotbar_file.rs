@@ -0,0 +1,202 @@
+use libxivdat::dat_file::read_content;
+
+use crate::model::CLASSJOB_NAMES;
+
+use std::path::Path;
+
+// The game doesn't document HOTBAR.DAT, so load_hotbars refuses a file this layout doesn't fit
+// exactly instead of showing whatever the bytes happen to say.
+//
+// Once libxivdat has stripped the file header and XOR mask, the content is a 16 byte header
+// followed by one layout for the shared bars and then one for every class and job, in ClassJob
+// order. A layout is 10 hotbars and then 8 cross hotbars, each stored as 16 slots (hotbars only
+// show 12 of them) of 5 bytes: the slot kind, then the id as a little-endian u32.
+const HOTBAR_CONTENT_HEADER_SIZE: usize = 16;
+const HOTBAR_SLOT_SIZE: usize = 5;
+const HOTBAR_SLOTS_PER_BAR: usize = 16;
+
+pub const HOTBAR_COUNT: usize = 10;
+pub const CROSS_HOTBAR_COUNT: usize = 8;
+pub const HOTBAR_VISIBLE_SLOTS: usize = 12;
+
+const BARS_PER_LAYOUT: usize = HOTBAR_COUNT + CROSS_HOTBAR_COUNT;
+const LAYOUT_SIZE: usize = BARS_PER_LAYOUT * HOTBAR_SLOTS_PER_BAR * HOTBAR_SLOT_SIZE;
+
+/// What a hotbar slot's id refers to. The values are the game's own, as listed in HotbarSlotType
+/// in FFXIVClientStructs (RaptureHotbarModule).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HotbarSlotKind {
+    Empty,
+    Action,
+    Item,
+    EventItem,
+    Emote,
+    Macro,
+    Marker,
+    CraftAction,
+    GeneralAction,
+    Companion,
+    Gearset,
+    Mount,
+    Unknown(u8),
+}
+
+impl From<u8> for HotbarSlotKind {
+    fn from(kind: u8) -> Self {
+        match kind {
+            0 => HotbarSlotKind::Empty,
+            1 => HotbarSlotKind::Action,
+            2 => HotbarSlotKind::Item,
+            3 => HotbarSlotKind::EventItem,
+            6 => HotbarSlotKind::Emote,
+            7 => HotbarSlotKind::Macro,
+            8 => HotbarSlotKind::Marker,
+            9 => HotbarSlotKind::CraftAction,
+            10 => HotbarSlotKind::GeneralAction,
+            13 => HotbarSlotKind::Companion,
+            15 => HotbarSlotKind::Gearset,
+            17 => HotbarSlotKind::Mount,
+            x => HotbarSlotKind::Unknown(x),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HotbarSlot {
+    pub kind: HotbarSlotKind,
+    pub id: u32,
+}
+
+impl HotbarSlot {
+    pub fn is_empty(&self) -> bool {
+        self.kind == HotbarSlotKind::Empty
+    }
+
+    /// For a macro slot, whether it's a shared macro and its index in the macro book. The book is
+    /// in the high byte of the id: 0 for individual (MACRO.DAT), 1 for shared (MACROSYS.DAT).
+    pub fn macro_index(&self) -> Option<(bool, usize)> {
+        if self.kind != HotbarSlotKind::Macro {
+            return None;
+        }
+        Some((self.id >> 8 == 1, (self.id & 0xff) as usize))
+    }
+}
+
+/// Ten hotbars followed by eight cross hotbars, sixteen slots each.
+pub type HotbarLayout = Vec<Vec<HotbarSlot>>;
+
+pub struct HotbarFile {
+    pub shared: HotbarLayout,
+    // Indexed by ClassJob id, so index 0 (adventurer) is never used.
+    pub class_jobs: Vec<HotbarLayout>,
+}
+
+fn parse_layout(bytes: &[u8]) -> HotbarLayout {
+    bytes
+        .chunks_exact(HOTBAR_SLOTS_PER_BAR * HOTBAR_SLOT_SIZE)
+        .map(|bar| {
+            bar.chunks_exact(HOTBAR_SLOT_SIZE)
+                .map(|slot| HotbarSlot {
+                    kind: HotbarSlotKind::from(slot[0]),
+                    id: u32::from_le_bytes([slot[1], slot[2], slot[3], slot[4]]),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reads a HOTBAR.DAT.
+pub fn load_hotbars(path: &Path) -> Result<HotbarFile, String> {
+    let content =
+        read_content(path).map_err(|err| format!("unable to read {:?}: {}", path, err))?;
+    parse_hotbars(&content).map_err(|err| format!("{:?} {}", path, err))
+}
+
+fn parse_hotbars(content: &[u8]) -> Result<HotbarFile, &'static str> {
+    let bars = content
+        .get(HOTBAR_CONTENT_HEADER_SIZE..)
+        .ok_or("is too short to be a HOTBAR.DAT")?;
+
+    // If the layout above is off, the bars won't line up with the end of the file.
+    if bars.len() % LAYOUT_SIZE != 0 {
+        return Err("isn't laid out like a HOTBAR.DAT");
+    }
+
+    let layouts = bars
+        .chunks_exact(LAYOUT_SIZE)
+        .map(parse_layout)
+        .collect::<Vec<_>>();
+
+    // The game only grows this file when new jobs come out, so anything short is damaged.
+    if layouts.len() < CLASSJOB_NAMES.len() {
+        return Err("is too short to be a HOTBAR.DAT");
+    }
+
+    let mut layouts = layouts.into_iter();
+    let shared = layouts.next().unwrap();
+    let mut class_jobs = vec![HotbarLayout::new()];
+    class_jobs.extend(layouts.take(CLASSJOB_NAMES.len() - 1));
+
+    Ok(HotbarFile { shared, class_jobs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_content(layouts: usize) -> Vec<u8> {
+        vec![0; HOTBAR_CONTENT_HEADER_SIZE + layouts * LAYOUT_SIZE]
+    }
+
+    fn slot_offset(layout: usize, bar: usize, slot: usize) -> usize {
+        HOTBAR_CONTENT_HEADER_SIZE
+            + layout * LAYOUT_SIZE
+            + (bar * HOTBAR_SLOTS_PER_BAR + slot) * HOTBAR_SLOT_SIZE
+    }
+
+    #[test]
+    fn slots_land_in_their_layout_and_bar() {
+        let mut content = empty_content(CLASSJOB_NAMES.len());
+        let at = slot_offset(0, 1, 2);
+        content[at..at + HOTBAR_SLOT_SIZE].copy_from_slice(&[1, 0x2c, 0x01, 0, 0]);
+        let at = slot_offset(24, HOTBAR_COUNT, 15);
+        content[at..at + HOTBAR_SLOT_SIZE].copy_from_slice(&[7, 3, 1, 0, 0]);
+
+        let hotbars = parse_hotbars(&content).unwrap();
+        assert_eq!(hotbars.shared.len(), HOTBAR_COUNT + CROSS_HOTBAR_COUNT);
+        assert_eq!(
+            hotbars.shared[1][2],
+            HotbarSlot {
+                kind: HotbarSlotKind::Action,
+                id: 300
+            }
+        );
+
+        let slot = hotbars.class_jobs[24][HOTBAR_COUNT][15];
+        assert_eq!(slot.kind, HotbarSlotKind::Macro);
+        assert_eq!(slot.macro_index(), Some((true, 3)));
+        assert!(hotbars.class_jobs[23]
+            .iter()
+            .flatten()
+            .all(HotbarSlot::is_empty));
+    }
+
+    #[test]
+    fn ignores_layouts_for_jobs_we_dont_know() {
+        let hotbars = parse_hotbars(&empty_content(CLASSJOB_NAMES.len() + 2)).unwrap();
+        assert_eq!(hotbars.class_jobs.len(), CLASSJOB_NAMES.len());
+    }
+
+    #[test]
+    fn rejects_short_files() {
+        assert!(parse_hotbars(&[0; 4]).is_err());
+        assert!(parse_hotbars(&empty_content(CLASSJOB_NAMES.len() - 1)).is_err());
+    }
+
+    #[test]
+    fn rejects_files_that_dont_line_up() {
+        let mut content = empty_content(CLASSJOB_NAMES.len());
+        content.push(0);
+        assert!(parse_hotbars(&content).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::importers::ImportError;
+use crate::model::{EquipSlot, ItemId, PortableGearset, PortableItem, EQUIPMENT_SLOT_COUNT};
+use serde_json::{Map, Value};
+
+//
+// {"name":"Machinist",
+//  "job":31,
+//  "weapon":42958,
+//  "head":44529,
+//  ...
+//  "fingerL":44818,
+//  "fingerR":43098,
+//  "materia":{"42958":{"1":41772,"2":41772},
+//             "44529":{"1":41772,"2":41771},
+//             ...
+//             "44818L":{"1":41771,"2":41773}}}
+//
+
+fn parse_materia<T: DataProvider>(
+    materia: Option<&Map<String, Value>>,
+    data_provider: &T,
+) -> Result<(Vec<u16>, Vec<u8>), ImportError> {
+    let mut materia_types = Vec::with_capacity(5);
+    let mut materia_grades = Vec::with_capacity(5);
+
+    let Some(materia) = materia else {
+        return Ok((materia_types, materia_grades));
+    };
+
+    // Slots are keyed "1" through "5".
+    for m in 1..=5 {
+        let Some(materia_item_id) = materia.get(&m.to_string()).and_then(Value::as_u64) else {
+            materia_types.push(0);
+            materia_grades.push(0);
+            continue;
+        };
+
+        let Ok((materia_type, materia_grade)) =
+            data_provider.find_materia_by_item_id(materia_item_id as u32)
+        else {
+            return Err(ImportError::MalformedData("unknown materia item"));
+        };
+        materia_types.push(materia_type as u16);
+        materia_grades.push(materia_grade);
+    }
+
+    Ok((materia_types, materia_grades))
+}
+
+/// Parses an Etro gearset, as returned by its API.
+pub fn parse_etro_json<T: DataProvider>(
+    json: &str,
+    data_provider: &T,
+) -> Result<PortableGearset, ImportError> {
+    let Ok(Value::Object(root)) = serde_json::from_str::<Value>(json) else {
+        return Err(ImportError::UnrecognizedFormat("not a JSON object"));
+    };
+
+    let Some(class_job) = root.get("job").and_then(Value::as_u64) else {
+        return Err(ImportError::MalformedData("missing job"));
+    };
+    let class_job =
+        u8::try_from(class_job).map_err(|_| ImportError::MalformedData("job out of range"))?;
+
+    let materia_map = root.get("materia").and_then(Value::as_object);
+
+    let mut equipment = vec![PortableItem::default(); EQUIPMENT_SLOT_COUNT];
+    for slot in EquipSlot::ALL {
+        let Some((label, materia_suffix)) = slot.etro_label() else {
+            continue;
+        };
+        let Some(item_id) = root.get(label).and_then(Value::as_u64) else {
+            continue;
+        };
+
+        let materia = materia_map
+            .and_then(|m| m.get(&format!("{}{}", item_id, materia_suffix)))
+            .and_then(Value::as_object);
+        let (materia_types, materia_grades) = parse_materia(materia, data_provider)?;
+
+        equipment[slot.index()] = PortableItem {
+            item_id: ItemId(item_id as u32),
+            materia_types,
+            materia_grades,
+        };
+    }
+
+    Ok(PortableGearset {
+        name: root
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("Etro")
+            .to_string(),
+        class_job,
+        equipment,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_provider::test_provider::TestProvider;
+
+    #[test]
+    fn items_and_materia() {
+        let json = r#"{
+            "name": "Machinist",
+            "job": 31,
+            "weapon": 42958,
+            "fingerL": 44818,
+            "fingerR": 44818,
+            "materia": {
+                "42958": {"1": 41761, "2": 41761},
+                "44818L": {"1": 41811},
+                "44818R": {"2": 41761}
+            }
+        }"#;
+
+        let gearset = parse_etro_json(json, &TestProvider).unwrap();
+        assert_eq!(gearset.name, "Machinist");
+        assert_eq!(gearset.class_job, 31);
+
+        let weapon = &gearset.equipment[EquipSlot::MainHand.index()];
+        assert_eq!(weapon.item_id, ItemId(42958));
+        assert_eq!(weapon.melds(), vec![(20, 11), (20, 11)]);
+
+        // The same ring on both fingers has its materia keyed by finger.
+        let left = &gearset.equipment[EquipSlot::LeftRing.index()];
+        assert_eq!(left.melds(), vec![(21, 11)]);
+        let right = &gearset.equipment[EquipSlot::RightRing.index()];
+        assert_eq!(right.materia_types, vec![0, 20, 0, 0, 0]);
+        assert_eq!(right.melds(), vec![(20, 11)]);
+    }
+
+    #[test]
+    fn rejects_out_of_range_job() {
+        let json = r#"{"job": 287, "weapon": 42958}"#;
+        assert!(matches!(
+            parse_etro_json(json, &TestProvider),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_materia() {
+        let json = r#"{"job": 31, "weapon": 42958, "materia": {"42958": {"1": 5}}}"#;
+        assert!(matches!(
+            parse_etro_json(json, &TestProvider),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+}
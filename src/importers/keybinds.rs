@@ -0,0 +1,77 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::importers::ImportError;
+use crate::keybind_file::Keybind;
+use serde_json::Value;
+
+/// Parses keybinds exported by `get_keybind_json`.
+pub fn parse_keybind_json(json: &str) -> Result<Vec<Keybind>, ImportError> {
+    let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(json) else {
+        return Err(ImportError::UnrecognizedFormat("not a JSON array"));
+    };
+
+    entries
+        .iter()
+        .map(|entry| {
+            let string_field = |name| entry.get(name).and_then(Value::as_str).map(str::to_string);
+
+            let Some(command) = string_field("command") else {
+                return Err(ImportError::MalformedData("keybind without a command"));
+            };
+
+            Ok(Keybind {
+                command,
+                primary: string_field("primary").unwrap_or_default(),
+                secondary: string_field("secondary").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::get_keybind_json;
+
+    #[test]
+    fn round_trip() {
+        let keybinds = vec![
+            Keybind {
+                command: "MOVE_FORE".to_string(),
+                primary: "57.0".to_string(),
+                secondary: "26.0".to_string(),
+            },
+            Keybind {
+                command: "JUMP".to_string(),
+                primary: "32.0".to_string(),
+                secondary: String::new(),
+            },
+        ];
+
+        let parsed = parse_keybind_json(&get_keybind_json(&keybinds)).unwrap();
+        assert_eq!(parsed, keybinds);
+    }
+
+    #[test]
+    fn missing_bindings_are_empty() {
+        let parsed = parse_keybind_json(r#"[{"command":"JUMP"}]"#).unwrap();
+        assert_eq!(parsed[0].primary, "");
+        assert_eq!(parsed[0].secondary, "");
+    }
+
+    #[test]
+    fn rejects_entries_without_a_command() {
+        assert!(matches!(
+            parse_keybind_json(r#"[{"primary":"32.0"}]"#),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_anything_but_an_array() {
+        assert!(matches!(
+            parse_keybind_json(r#"{"command":"JUMP"}"#),
+            Err(ImportError::UnrecognizedFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::importers::ImportError;
+use libxivdat::xiv_macro::Macro;
+use serde_json::Value;
+
+// A macro book always has 100 slots, each with 15 lines.
+const MACRO_SLOT_COUNT: usize = 100;
+const MACRO_LINE_COUNT: usize = 15;
+
+/// Parses macros exported by `get_macro_json`, returning each one with the slot it belongs in.
+pub fn parse_macro_json(json: &str) -> Result<Vec<(usize, Macro)>, ImportError> {
+    let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(json) else {
+        return Err(ImportError::UnrecognizedFormat("not a JSON array"));
+    };
+
+    entries
+        .iter()
+        .map(|entry| {
+            let Some(index) = entry.get("index").and_then(Value::as_u64) else {
+                return Err(ImportError::MalformedData("macro without an index"));
+            };
+            if index as usize >= MACRO_SLOT_COUNT {
+                return Err(ImportError::MalformedData("macro index out of range"));
+            }
+
+            let string_field = |name| {
+                entry
+                    .get(name)
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string()
+            };
+
+            let mut lines: Vec<String> = entry
+                .get("lines")
+                .and_then(Value::as_array)
+                .map(|lines| {
+                    lines
+                        .iter()
+                        .map(|line| line.as_str().unwrap_or("").to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            if lines.len() > MACRO_LINE_COUNT {
+                return Err(ImportError::MalformedData("macro has too many lines"));
+            }
+            lines.resize(MACRO_LINE_COUNT, String::new());
+
+            Ok((
+                index as usize,
+                Macro {
+                    icon_id: string_field("iconId"),
+                    icon_key: string_field("iconKey"),
+                    lines,
+                    title: string_field("title"),
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::get_macro_json;
+
+    fn empty_macro() -> Macro {
+        Macro {
+            icon_id: String::new(),
+            icon_key: String::new(),
+            lines: vec![String::new(); MACRO_LINE_COUNT],
+            title: String::new(),
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_slots() {
+        let mut macros = vec![empty_macro(); 3];
+        macros[2] = Macro {
+            icon_id: "0B1".to_string(),
+            icon_key: "00101D1".to_string(),
+            lines: vec!["/ac \"Reflect\" <wait.2>".to_string(); MACRO_LINE_COUNT],
+            title: "Opener".to_string(),
+        };
+
+        let parsed = parse_macro_json(&get_macro_json(&macros)).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let (index, m) = &parsed[0];
+        assert_eq!(*index, 2);
+        assert_eq!(m.title, "Opener");
+        assert_eq!(m.icon_id, "0B1");
+        assert_eq!(m.icon_key, "00101D1");
+        assert_eq!(m.lines, macros[2].lines);
+    }
+
+    #[test]
+    fn short_macros_are_padded() {
+        let parsed = parse_macro_json(r#"[{"index":0,"lines":["/echo hi"]}]"#).unwrap();
+        assert_eq!(parsed[0].1.lines.len(), MACRO_LINE_COUNT);
+        assert_eq!(parsed[0].1.lines[0], "/echo hi");
+    }
+
+    #[test]
+    fn rejects_out_of_range_index() {
+        assert!(matches!(
+            parse_macro_json(r#"[{"index":100}]"#),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_too_many_lines() {
+        let json = format!(
+            r#"[{{"index":0,"lines":{:?}}}]"#,
+            vec![""; MACRO_LINE_COUNT + 1]
+        );
+        assert!(matches!(
+            parse_macro_json(&json),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+}
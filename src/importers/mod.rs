@@ -0,0 +1,62 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use std::error::Error;
+use std::fmt;
+
+use crate::data_provider::DataProvider;
+use crate::model::PortableGearset;
+
+mod etro;
+mod keybinds;
+mod macros;
+mod share_code;
+mod xivgear;
+
+pub use etro::parse_etro_json;
+pub use keybinds::parse_keybind_json;
+pub use macros::parse_macro_json;
+pub use share_code::parse_share_code;
+pub use xivgear::parse_xivgear_json;
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum ImportError {
+    UnrecognizedFormat(&'static str),
+    MalformedData(&'static str),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::UnrecognizedFormat(desc) => write!(f, "Unrecognized format: {}", desc),
+            ImportError::MalformedData(desc) => write!(f, "Malformed data: {}", desc),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+/// Imports whatever the user pasted, guessing the format from what it looks like.
+pub fn parse_import<T: DataProvider>(
+    text: &str,
+    data_provider: &T,
+) -> Result<Vec<PortableGearset>, ImportError> {
+    if text.trim_start().starts_with('{') {
+        // Etro exports use one top-level key per slot, xivgear puts everything under "items"
+        // or "sets".
+        let is_etro = serde_json::from_str::<serde_json::Value>(text)
+            .map(|value| value.get("weapon").is_some())
+            .unwrap_or(false);
+        if is_etro {
+            Ok(vec![parse_etro_json(text, data_provider)?])
+        } else {
+            parse_xivgear_json(text, data_provider)
+        }
+    } else {
+        Ok(vec![parse_share_code(text)?])
+    }
+}
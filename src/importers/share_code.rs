@@ -0,0 +1,151 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::exporters::SHARE_CODE_PREFIX;
+use crate::importers::ImportError;
+use crate::model::{ItemId, PortableGearset, PortableItem, EQUIPMENT_SLOT_COUNT};
+
+fn from_base36(field: &str) -> Result<u32, ImportError> {
+    u32::from_str_radix(field, 36)
+        .map_err(|_| ImportError::MalformedData("expected a base36 number"))
+}
+
+fn parse_slot(field: &str) -> Result<PortableItem, ImportError> {
+    if field.is_empty() {
+        return Ok(PortableItem::default());
+    }
+
+    let mut numbers = field.split(',');
+    let item_id = ItemId(from_base36(numbers.next().unwrap_or(""))?);
+
+    let mut materia_types = Vec::with_capacity(5);
+    let mut materia_grades = Vec::with_capacity(5);
+    while let Some(materia_type) = numbers.next() {
+        let Some(materia_grade) = numbers.next() else {
+            return Err(ImportError::MalformedData("materia type without a grade"));
+        };
+        materia_types.push(
+            u16::try_from(from_base36(materia_type)?)
+                .map_err(|_| ImportError::MalformedData("materia type out of range"))?,
+        );
+        materia_grades.push(
+            u8::try_from(from_base36(materia_grade)?)
+                .map_err(|_| ImportError::MalformedData("materia grade out of range"))?,
+        );
+    }
+
+    Ok(PortableItem {
+        item_id,
+        materia_types,
+        materia_grades,
+    })
+}
+
+/// Decodes a code produced by `get_share_code` back into a gearset.
+pub fn parse_share_code(code: &str) -> Result<PortableGearset, ImportError> {
+    let mut fields = code.trim().split('.');
+
+    if fields.next() != Some(SHARE_CODE_PREFIX) {
+        return Err(ImportError::UnrecognizedFormat("not a roarich share code"));
+    }
+
+    let Some(class_job) = fields.next() else {
+        return Err(ImportError::MalformedData("missing job"));
+    };
+    let class_job = u8::try_from(from_base36(class_job)?)
+        .map_err(|_| ImportError::MalformedData("job out of range"))?;
+
+    let mut equipment = fields.map(parse_slot).collect::<Result<Vec<_>, _>>()?;
+    if equipment.len() > EQUIPMENT_SLOT_COUNT {
+        return Err(ImportError::MalformedData("too many equipment slots"));
+    }
+    equipment.resize_with(EQUIPMENT_SLOT_COUNT, Default::default);
+
+    Ok(PortableGearset {
+        name: "Shared gearset".to_string(),
+        class_job,
+        equipment,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::get_share_code;
+
+    fn sample_gearset() -> PortableGearset {
+        let mut equipment = vec![PortableItem::default(); EQUIPMENT_SLOT_COUNT];
+        equipment[0] = PortableItem {
+            item_id: ItemId(43118),
+            materia_types: vec![20, 20],
+            materia_grades: vec![11, 11],
+        };
+        // HQ crafted gear with a full set of overmelds.
+        equipment[2] = PortableItem {
+            item_id: ItemId(1_043_142),
+            materia_types: vec![20, 21, 20, 21, 20],
+            materia_grades: vec![11, 11, 10, 9, 9],
+        };
+        equipment[12] = PortableItem {
+            item_id: ItemId(43153),
+            materia_types: Vec::new(),
+            materia_grades: Vec::new(),
+        };
+
+        PortableGearset {
+            name: "Shared gearset".to_string(),
+            class_job: 31,
+            equipment,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let gearset = sample_gearset();
+        let code = get_share_code(&gearset);
+        assert_eq!(parse_share_code(&code).unwrap(), gearset);
+    }
+
+    #[test]
+    fn round_trip_ignores_surrounding_whitespace() {
+        let gearset = sample_gearset();
+        let code = format!("  {}\n", get_share_code(&gearset));
+        assert_eq!(parse_share_code(&code).unwrap(), gearset);
+    }
+
+    fn is_malformed(code: &str) -> bool {
+        matches!(parse_share_code(code), Err(ImportError::MalformedData(_)))
+    }
+
+    #[test]
+    fn rejects_other_prefixes() {
+        assert!(matches!(
+            parse_share_code("rch0.v"),
+            Err(ImportError::UnrecognizedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_out_of_range_job() {
+        // 256 in base36.
+        assert!(is_malformed("rch1.74"));
+    }
+
+    #[test]
+    fn rejects_out_of_range_materia() {
+        // A grade of 256 would otherwise wrap around to 0.
+        assert!(is_malformed("rch1.v.wvy,k,74"));
+        // As would a type of 65536.
+        assert!(is_malformed("rch1.v.wvy,1ekg,b"));
+    }
+
+    #[test]
+    fn rejects_dangling_materia_type() {
+        assert!(is_malformed("rch1.v.wvy,k"));
+    }
+
+    #[test]
+    fn rejects_too_many_slots() {
+        let code = format!("rch1.v{}", ".".repeat(EQUIPMENT_SLOT_COUNT + 1));
+        assert!(is_malformed(&code));
+    }
+}
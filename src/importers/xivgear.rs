@@ -0,0 +1,198 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::importers::ImportError;
+use crate::model::{
+    EquipSlot, ItemId, PortableGearset, PortableItem, CLASSJOB_NAMES, EQUIPMENT_SLOT_COUNT,
+};
+use serde_json::{Map, Value};
+
+fn parse_item<T: DataProvider>(
+    item: &Value,
+    data_provider: &T,
+) -> Result<PortableItem, ImportError> {
+    let Some(item_id) = item.get("id").and_then(Value::as_u64) else {
+        return Err(ImportError::MalformedData("item without an id"));
+    };
+
+    let mut materia_types = Vec::with_capacity(5);
+    let mut materia_grades = Vec::with_capacity(5);
+    if let Some(materia) = item.get("materia").and_then(Value::as_array) {
+        for m in materia {
+            // xivgear uses -1 for an empty slot
+            let materia_item_id = m.get("id").and_then(Value::as_i64).unwrap_or(-1);
+            if materia_item_id <= 0 {
+                materia_types.push(0);
+                materia_grades.push(0);
+                continue;
+            }
+
+            let Ok((materia_type, materia_grade)) =
+                data_provider.find_materia_by_item_id(materia_item_id as u32)
+            else {
+                return Err(ImportError::MalformedData("unknown materia item"));
+            };
+            materia_types.push(materia_type as u16);
+            materia_grades.push(materia_grade);
+        }
+    }
+
+    // Relic stats ("relicStats") can't be mapped back to a specific materia type and grade
+    // without knowing the weapon, so they're dropped.
+
+    Ok(PortableItem {
+        item_id: ItemId(item_id as u32),
+        materia_types,
+        materia_grades,
+    })
+}
+
+fn parse_set<T: DataProvider>(
+    set: &Map<String, Value>,
+    default_name: &str,
+    class_job: u8,
+    data_provider: &T,
+) -> Result<PortableGearset, ImportError> {
+    let mut equipment = vec![PortableItem::default(); EQUIPMENT_SLOT_COUNT];
+
+    if let Some(items) = set.get("items").and_then(Value::as_object) {
+        for (label, item) in items {
+            // Unknown labels (food, etc.) aren't equipment.
+            let Some(slot) = EquipSlot::ALL
+                .iter()
+                .find(|slot| slot.xivgear_label() == Some(label.as_str()))
+            else {
+                continue;
+            };
+            equipment[slot.index()] = parse_item(item, data_provider)?;
+        }
+    }
+
+    Ok(PortableGearset {
+        name: set
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(default_name)
+            .to_string(),
+        class_job,
+        equipment,
+    })
+}
+
+/// Parses an xivgear set or sheet export. A sheet results in one gearset per set on it.
+pub fn parse_xivgear_json<T: DataProvider>(
+    json: &str,
+    data_provider: &T,
+) -> Result<Vec<PortableGearset>, ImportError> {
+    let Ok(Value::Object(root)) = serde_json::from_str::<Value>(json) else {
+        return Err(ImportError::UnrecognizedFormat("not a JSON object"));
+    };
+
+    let Some(job) = root.get("job").and_then(Value::as_str) else {
+        return Err(ImportError::MalformedData("missing job"));
+    };
+    let Some(class_job) = CLASSJOB_NAMES.iter().position(|x| *x == job) else {
+        return Err(ImportError::MalformedData("unknown job"));
+    };
+
+    let name = root
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("xivgear");
+
+    match root.get("sets").and_then(Value::as_array) {
+        // A whole sheet
+        Some(sets) => sets
+            .iter()
+            .filter_map(Value::as_object)
+            .map(|set| parse_set(set, name, class_job as u8, data_provider))
+            .collect(),
+        // A single set
+        None => Ok(vec![parse_set(
+            &root,
+            name,
+            class_job as u8,
+            data_provider,
+        )?]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_provider::test_provider::TestProvider;
+
+    #[test]
+    fn single_set() {
+        let json = r#"{
+            "name": "2.50",
+            "job": "MCH",
+            "items": {
+                "Weapon": {"id": 43118, "materia": [{"id": 41761}, {"id": -1}]},
+                "RingLeft": {"id": 43153, "materia": [{"id": 41811}]},
+                "Food": {"id": 44091}
+            }
+        }"#;
+
+        let gearsets = parse_xivgear_json(json, &TestProvider).unwrap();
+        assert_eq!(gearsets.len(), 1);
+
+        let gearset = &gearsets[0];
+        assert_eq!(gearset.name, "2.50");
+        assert_eq!(gearset.class_job, 31);
+        assert_eq!(gearset.equipment.len(), EQUIPMENT_SLOT_COUNT);
+        assert_eq!(
+            gearset.equipment[EquipSlot::MainHand.index()],
+            PortableItem {
+                item_id: ItemId(43118),
+                materia_types: vec![20, 0],
+                materia_grades: vec![11, 0],
+            }
+        );
+        assert_eq!(
+            gearset.equipment[EquipSlot::LeftRing.index()].item_id,
+            ItemId(43153)
+        );
+        assert_eq!(
+            gearset.equipment[EquipSlot::LeftRing.index()].materia_types,
+            vec![21]
+        );
+    }
+
+    #[test]
+    fn sheet_with_several_sets() {
+        let json = r#"{
+            "name": "Savage prog",
+            "job": "WAR",
+            "sets": [
+                {"name": "BiS", "items": {"Weapon": {"id": 43113}}},
+                {"items": {"Weapon": {"id": 43120}}}
+            ]
+        }"#;
+
+        let gearsets = parse_xivgear_json(json, &TestProvider).unwrap();
+        assert_eq!(gearsets.len(), 2);
+        assert_eq!(gearsets[0].name, "BiS");
+        // Sets without a name fall back to the sheet's.
+        assert_eq!(gearsets[1].name, "Savage prog");
+        assert!(gearsets.iter().all(|gs| gs.class_job == 21));
+    }
+
+    #[test]
+    fn rejects_unknown_materia() {
+        let json = r#"{"job": "MCH", "items": {"Weapon": {"id": 1, "materia": [{"id": 5}]}}}"#;
+        assert!(matches!(
+            parse_xivgear_json(json, &TestProvider),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_job() {
+        let json = r#"{"job": "ABC", "items": {}}"#;
+        assert!(matches!(
+            parse_xivgear_json(json, &TestProvider),
+            Err(ImportError::MalformedData(_))
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use libxivdat::dat_file::read_content;
+
+use crate::model::EquipSlot;
+
+use std::path::Path;
+
+// ITEMODR.DAT, once libxivdat has stripped the header and XOR mask, is a run of records, each a
+// one-byte tag, a little-endian u32 payload size and the payload.
+const RECORD_HEADER_SIZE: usize = 5;
+// Starts a sorted container; the slot records that follow belong to it.
+const CONTAINER_TAG: u8 = b'n';
+// One sorted position; the payload is the slot and container it shows, both u16.
+const SLOT_TAG: u8 = b'i';
+// Starts a retainer's containers; the payload is the retainer's id.
+const RETAINER_TAG: u8 = b'r';
+
+// The player's own containers come first, in this order.
+const PLAYER_CONTAINER_NAMES: [&str; 14] = [
+    "Inventory",
+    "Armoury: Main Hand",
+    "Armoury: Head",
+    "Armoury: Body",
+    "Armoury: Hands",
+    "Armoury: Legs",
+    "Armoury: Feet",
+    "Armoury: Off Hand",
+    "Armoury: Earrings",
+    "Armoury: Necklace",
+    "Armoury: Bracelets",
+    "Armoury: Rings",
+    "Armoury: Soul Crystals",
+    "Saddlebag",
+];
+
+// The gear slots each of the player's containers holds items for, which is nothing unless it's an
+// armoury chest.
+const PLAYER_CONTAINER_SLOTS: [&[EquipSlot]; 14] = [
+    &[],
+    &[EquipSlot::MainHand],
+    &[EquipSlot::Head],
+    &[EquipSlot::Body],
+    &[EquipSlot::Hands],
+    &[EquipSlot::Legs],
+    &[EquipSlot::Feet],
+    &[EquipSlot::OffHand],
+    &[EquipSlot::Ears],
+    &[EquipSlot::Neck],
+    &[EquipSlot::Wrists],
+    &[EquipSlot::LeftRing, EquipSlot::RightRing],
+    &[EquipSlot::SoulCrystal],
+    &[],
+];
+
+/// Where the item shown at a sorted position really lives.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ItemSlot {
+    pub container: u16,
+    pub slot: u16,
+}
+
+pub struct SortedContainer {
+    pub name: String,
+    /// For an armoury chest, the gear slots its items go in.
+    pub equip_slots: &'static [EquipSlot],
+    pub slots: Vec<ItemSlot>,
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Reads an ITEMODR.DAT.
+///
+/// The file only records how the game sorts each container, not what's in it; item ids live on
+/// the server.
+pub fn load_item_order(path: &Path) -> Result<Vec<SortedContainer>, String> {
+    let content =
+        read_content(path).map_err(|err| format!("unable to read {:?}: {}", path, err))?;
+    let malformed = || format!("{:?} is not a valid ITEMODR.DAT", path);
+
+    let mut containers: Vec<SortedContainer> = Vec::new();
+    let mut retainer_count = 0;
+    let mut retainer_container_count = 0;
+    let mut offset = 0;
+    while offset < content.len() {
+        let tag = content[offset];
+        let size = read_u32(&content, offset + 1).ok_or_else(malformed)? as usize;
+        let payload = content
+            .get(offset + RECORD_HEADER_SIZE..offset + RECORD_HEADER_SIZE + size)
+            .ok_or_else(malformed)?;
+        offset += RECORD_HEADER_SIZE + size;
+
+        match tag {
+            RETAINER_TAG => {
+                retainer_count += 1;
+                retainer_container_count = 0;
+            }
+            CONTAINER_TAG => {
+                let (name, equip_slots) = if retainer_count == 0 {
+                    let index = containers.len();
+                    (
+                        PLAYER_CONTAINER_NAMES
+                            .get(index)
+                            .map(|name| name.to_string())
+                            .unwrap_or_else(|| format!("Container {}", index)),
+                        PLAYER_CONTAINER_SLOTS
+                            .get(index)
+                            .copied()
+                            .unwrap_or_default(),
+                    )
+                } else {
+                    retainer_container_count += 1;
+                    (
+                        format!("Retainer {} ({})", retainer_count, retainer_container_count),
+                        &[][..],
+                    )
+                };
+                containers.push(SortedContainer {
+                    name,
+                    equip_slots,
+                    slots: Vec::new(),
+                });
+            }
+            SLOT_TAG => {
+                let container = containers.last_mut().ok_or_else(malformed)?;
+                container.slots.push(ItemSlot {
+                    slot: read_u16(payload, 0).ok_or_else(malformed)?,
+                    container: read_u16(payload, 2).ok_or_else(malformed)?,
+                });
+            }
+            // Anything else (the version record, mostly) doesn't affect the layout.
+            _ => {}
+        }
+    }
+
+    Ok(containers)
+}
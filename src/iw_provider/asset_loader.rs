@@ -56,6 +56,33 @@ fn read_bgra8(texture: tex::Texture) -> Result<DynamicImage, LoadError> {
     Ok(DynamicImage::ImageRgba8(buffer))
 }
 
+/// Loads a 2D texture out of the game files and decodes it.
+pub fn decode_texture(ironworks: &Ironworks, path: &str) -> Result<DynamicImage, LoadError> {
+    let Ok(texture) = ironworks.file::<tex::Texture>(path) else {
+        return Err(LoadError::Loading("ironworks load error".into()));
+    };
+
+    if !matches!(texture.kind(), tex::TextureKind::D2) {
+        return Err(LoadError::FormatNotSupported {
+            detected_format: Some(format!("texture kind: {:?}", texture.kind())),
+        });
+    }
+
+    match texture.format() {
+        tex::Format::Bgra8Unorm => read_bgra8(texture),
+        tex::Format::Bc1Unorm => read_dds(texture, image_dds::ImageFormat::BC1RgbaUnorm),
+        tex::Format::Bc2Unorm => read_dds(texture, image_dds::ImageFormat::BC2RgbaUnorm),
+        tex::Format::Bc3Unorm => read_dds(texture, image_dds::ImageFormat::BC3RgbaUnorm),
+        tex::Format::Bc4Unorm => read_dds(texture, image_dds::ImageFormat::BC4RUnorm),
+        tex::Format::Bc5Unorm => read_dds(texture, image_dds::ImageFormat::BC5RgUnorm),
+        tex::Format::Bc6hFloat => read_dds(texture, image_dds::ImageFormat::BC6hRgbSfloat),
+        tex::Format::Bc7Unorm => read_dds(texture, image_dds::ImageFormat::BC7RgbaUnorm),
+        _ => Err(LoadError::FormatNotSupported {
+            detected_format: Some(format!("texture format: {:?}", texture.format())),
+        }),
+    }
+}
+
 #[derive(Default)]
 pub struct AssetLoader {
     ironworks: Arc<Ironworks>,
@@ -92,35 +119,10 @@ impl BytesLoader for AssetLoader {
         if let Some(cache_entry) = cache.get(uri).cloned() {
             return Ok(cache_entry.clone());
         } else {
-            let Ok(texture) = self.ironworks.file::<tex::Texture>(path) else {
-                return Err(LoadError::Loading("ironworks load error".into()));
-            };
-
-            if !matches!(texture.kind(), tex::TextureKind::D2) {
-                return Err(LoadError::FormatNotSupported {
-                    detected_format: Some(format!("texture kind: {:?}", texture.kind())),
-                });
-            }
-
+            let dynimage = decode_texture(&self.ironworks, path)?;
             let texture_size = Vec2 {
-                x: texture.width().into(),
-                y: texture.height().into(),
-            };
-
-            let dynimage = match texture.format() {
-                tex::Format::Bgra8Unorm => read_bgra8(texture)?,
-                tex::Format::Bc1Unorm => read_dds(texture, image_dds::ImageFormat::BC1RgbaUnorm)?,
-                tex::Format::Bc2Unorm => read_dds(texture, image_dds::ImageFormat::BC2RgbaUnorm)?,
-                tex::Format::Bc3Unorm => read_dds(texture, image_dds::ImageFormat::BC3RgbaUnorm)?,
-                tex::Format::Bc4Unorm => read_dds(texture, image_dds::ImageFormat::BC4RUnorm)?,
-                tex::Format::Bc5Unorm => read_dds(texture, image_dds::ImageFormat::BC5RgUnorm)?,
-                tex::Format::Bc6hFloat => read_dds(texture, image_dds::ImageFormat::BC6hRgbSfloat)?,
-                tex::Format::Bc7Unorm => read_dds(texture, image_dds::ImageFormat::BC7RgbaUnorm)?,
-                _ => {
-                    return Err(LoadError::FormatNotSupported {
-                        detected_format: Some(format!("texture format: {:?}", texture.format())),
-                    });
-                }
+                x: dynimage.width() as f32,
+                y: dynimage.height() as f32,
             };
 
             let mut bytes = Cursor::new(vec![]);
@@ -1,19 +1,23 @@
 use crate::data_provider::{DataProvider, DataProviderError};
 use crate::directories;
-use crate::iw_provider::asset_loader::AssetLoader;
-use crate::model::{Item, Materia};
+use crate::iw_provider::asset_loader::{decode_texture, AssetLoader};
+use crate::model::{Action, Item, ItemId, ItemKind, Materia, Stain};
 
 use egui::ImageSource;
+use image::RgbaImage;
 use ironworks::{
-    excel::{Excel, Field, Language},
+    excel::{Excel, Field, Language, Row},
     sqpack::{Install, SqPack},
     Ironworks,
 };
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Default)]
 pub struct IronworksProvider {
     ironworks: Arc<Ironworks>,
+    // Materia item id -> (materia type, grade), built the first time a meld is imported.
+    materia_by_item: OnceLock<HashMap<u32, (u32, u8)>>,
 }
 
 impl IronworksProvider {
@@ -33,6 +37,44 @@ impl IronworksProvider {
         }
     }
 
+    // Walks every row of the Materia sheet, whatever the row ids are.
+    fn load_materia_by_item(&self) -> Result<HashMap<u32, (u32, u8)>, DataProviderError> {
+        let excel =
+            Excel::new(Arc::clone(&self.ironworks)).with_default_language(Language::English);
+
+        let mut materia_by_item = HashMap::new();
+        for row in excel.sheet("Materia")?.into_iter() {
+            let materia = materia_from_row(row.row_id(), &row)?;
+            for (grade, item_id) in materia.item_id.iter().enumerate() {
+                if *item_id != 0 {
+                    materia_by_item.insert(*item_id, (materia.id, grade as u8));
+                }
+            }
+        }
+
+        Ok(materia_by_item)
+    }
+
+    // Action, GeneralAction, Emote and CraftAction all boil down to a name and an icon as far as
+    // we're concerned.
+    fn get_named_icon(
+        &self,
+        sheet: &str,
+        id: u32,
+        name_column: usize,
+        icon_column: usize,
+    ) -> Result<Action, DataProviderError> {
+        let excel =
+            Excel::new(Arc::clone(&self.ironworks)).with_default_language(Language::English);
+        let row = excel.sheet(sheet)?.row(id)?;
+
+        Ok(Action {
+            id,
+            name: field_to_string(row.field(name_column)?)?,
+            icon: ui_icon_path(field_to_u32(row.field(icon_column)?)?),
+        })
+    }
+
     pub fn install_bytes_loader(&self, ctx: &egui::Context) {
         if !ctx.is_loader_installed(AssetLoader::ID) {
             ctx.add_bytes_loader(std::sync::Arc::new(AssetLoader::new(
@@ -58,6 +100,24 @@ fn field_to_string(field: Field) -> Result<String, DataProviderError> {
     }
 }
 
+fn field_to_u8(field: Field) -> Result<u8, DataProviderError> {
+    match field {
+        Field::String(_) => Err(DataProviderError::FieldTypeMismatch(
+            "conversion of string to u8",
+        )),
+        Field::Bool(x) => Ok(x as u8),
+        Field::I8(x) => Ok(x as u8),
+        Field::I16(x) => Ok(x as u8),
+        Field::I32(x) => Ok(x as u8),
+        Field::I64(x) => Ok(x as u8),
+        Field::U8(x) => Ok(x as u8),
+        Field::U16(x) => Ok(x as u8),
+        Field::U32(x) => Ok(x as u8),
+        Field::U64(x) => Ok(x as u8),
+        Field::F32(x) => Ok(x as u8),
+    }
+}
+
 fn field_to_u16(field: Field) -> Result<u16, DataProviderError> {
     match field {
         Field::String(_) => Err(DataProviderError::FieldTypeMismatch(
@@ -130,34 +190,144 @@ fn field_to_i32(field: Field) -> Result<i32, DataProviderError> {
     }
 }
 
+// Column indices in the Item sheet.
+const ITEM_DESCRIPTION: usize = 8;
+const ITEM_NAME: usize = 9;
+const ITEM_ICON: usize = 10;
+const ITEM_LEVEL_ITEM: usize = 11;
+const ITEM_EQUIP_SLOT_CATEGORY: usize = 17;
+const ITEM_IS_UNIQUE: usize = 21;
+const ITEM_IS_UNTRADABLE: usize = 22;
+const ITEM_CAN_BE_HQ: usize = 27;
+const ITEM_DYE_COUNT: usize = 28;
+const ITEM_IS_CREST_WORTHY: usize = 29;
+const ITEM_LEVEL_EQUIP: usize = 40;
+const ITEM_CLASS_JOB_CATEGORY: usize = 43;
+const ITEM_DAMAGE_PHYS: usize = 51;
+const ITEM_DAMAGE_MAG: usize = 52;
+const ITEM_DELAY_MS: usize = 53;
+const ITEM_BLOCK_RATE: usize = 55;
+const ITEM_BLOCK: usize = 56;
+const ITEM_DEFENSE_PHYS: usize = 57;
+const ITEM_DEFENSE_MAG: usize = 58;
+// BaseParam[n] and BaseParamValue[n] are interleaved, six pairs of them.
+const ITEM_BASE_PARAM: usize = 59;
+const ITEM_BASE_PARAM_SPECIAL: usize = 73;
+const ITEM_BASE_PARAM_COUNT: usize = 6;
+const ITEM_MATERIA_SLOT_COUNT: usize = 86;
+const ITEM_IS_ADVANCED_MELDING_PERMITTED: usize = 87;
+
+// Column indices in the Stain sheet.
+const STAIN_COLOR: usize = 0;
+const STAIN_NAME: usize = 3;
+
+// Column indices in the sheets that hotbar slots point into.
+const ACTION_NAME: usize = 0;
+const ACTION_ICON: usize = 2;
+const GENERAL_ACTION_NAME: usize = 0;
+const GENERAL_ACTION_ICON: usize = 7;
+const EMOTE_NAME: usize = 0;
+const EMOTE_ICON: usize = 20;
+const CRAFT_ACTION_NAME: usize = 0;
+const CRAFT_ACTION_ICON: usize = 4;
+
+fn materia_from_row(id: u32, row: &Row) -> Result<Materia, DataProviderError> {
+    let mut item_id_vec = Vec::with_capacity(16);
+    let mut base_param_value_vec = Vec::with_capacity(16);
+    for i in 0..16 {
+        item_id_vec.push(field_to_u32(row.field(i)?)?);
+        base_param_value_vec.push(field_to_i16(row.field(i + 17)?)?);
+    }
+
+    Ok(Materia {
+        id,
+        item_id: item_id_vec,
+        base_param_id: field_to_i32(row.field(16)?)?,
+        base_param_value: base_param_value_vec,
+    })
+}
+
 // ui/icon/051000/051474_hr1.tex
 fn ui_icon_path(id: u32) -> String {
     format!("ui/icon/{:0>6}/{:0>6}_hr1.tex", id - (id % 1000), id)
 }
 
 impl DataProvider for IronworksProvider {
-    fn get_item(&self, item_id: u32) -> Result<Item, DataProviderError> {
-        // HQ items are represented as ids above 1000000
-        if item_id >= 1000000 {
-            let nq_item = self.get_item(item_id - 1000000)?;
+    fn get_item(&self, item_id: ItemId) -> Result<Item, DataProviderError> {
+        let suffix = match item_id.kind() {
+            ItemKind::Normal => None,
+            ItemKind::HighQuality => Some("HQ"),
+            ItemKind::Collectable => Some("Collectable"),
+            ItemKind::Event => {
+                return Err(DataProviderError::ObjectNotFound(
+                    "key items aren't in the Item sheet",
+                ))
+            }
+        };
+        if let Some(suffix) = suffix {
+            let base_item = self.get_item(item_id.base())?;
 
             return Ok(Item {
                 id: item_id,
-                name: format!("{} (HQ)", nq_item.name),
+                name: format!("{} ({})", base_item.name, suffix),
 
-                ..nq_item
+                ..base_item
             });
         }
 
         let excel =
             Excel::new(Arc::clone(&self.ironworks)).with_default_language(Language::English);
         let items = excel.sheet("Item")?;
-        let row = items.row(item_id)?;
+        let row = items.row(item_id.base_id())?;
+
+        let mut base_param_ids = Vec::with_capacity(ITEM_BASE_PARAM_COUNT);
+        let mut base_param_values = Vec::with_capacity(ITEM_BASE_PARAM_COUNT);
+        let mut base_param_special_ids = Vec::with_capacity(ITEM_BASE_PARAM_COUNT);
+        let mut base_param_special_values = Vec::with_capacity(ITEM_BASE_PARAM_COUNT);
+        for i in 0..ITEM_BASE_PARAM_COUNT {
+            let param = field_to_i32(row.field(ITEM_BASE_PARAM + i * 2)?)?;
+            if param != 0 {
+                base_param_ids.push(param);
+                base_param_values.push(field_to_i16(row.field(ITEM_BASE_PARAM + i * 2 + 1)?)?);
+            }
+
+            let special_param = field_to_i32(row.field(ITEM_BASE_PARAM_SPECIAL + i * 2)?)?;
+            if special_param != 0 {
+                base_param_special_ids.push(special_param);
+                base_param_special_values.push(field_to_i16(
+                    row.field(ITEM_BASE_PARAM_SPECIAL + i * 2 + 1)?,
+                )?);
+            }
+        }
 
         Ok(Item {
             id: item_id,
-            name: field_to_string(row.field(9)?)?,
-            icon: ui_icon_path(field_to_u32(row.field(10)?)?),
+            name: field_to_string(row.field(ITEM_NAME)?)?,
+            description: field_to_string(row.field(ITEM_DESCRIPTION)?)?,
+            icon: ui_icon_path(field_to_u32(row.field(ITEM_ICON)?)?),
+            level_item: field_to_u16(row.field(ITEM_LEVEL_ITEM)?)?,
+            damage_phys: field_to_u16(row.field(ITEM_DAMAGE_PHYS)?)?,
+            damage_mag: field_to_u16(row.field(ITEM_DAMAGE_MAG)?)?,
+            delay_ms: field_to_u16(row.field(ITEM_DELAY_MS)?)?,
+            block_rate: field_to_u16(row.field(ITEM_BLOCK_RATE)?)?,
+            block: field_to_u16(row.field(ITEM_BLOCK)?)?,
+            defense_phys: field_to_u16(row.field(ITEM_DEFENSE_PHYS)?)?,
+            defense_mag: field_to_u16(row.field(ITEM_DEFENSE_MAG)?)?,
+            level_equip: field_to_u8(row.field(ITEM_LEVEL_EQUIP)?)?,
+            equip_slot_category: field_to_u8(row.field(ITEM_EQUIP_SLOT_CATEGORY)?)?,
+            class_job_category: field_to_u32(row.field(ITEM_CLASS_JOB_CATEGORY)?)?,
+            base_param_ids,
+            base_param_values,
+            base_param_special_ids,
+            base_param_special_values,
+            materia_slot_count: field_to_u8(row.field(ITEM_MATERIA_SLOT_COUNT)?)?,
+            advanced_melds_permitted: field_to_u8(row.field(ITEM_IS_ADVANCED_MELDING_PERMITTED)?)?
+                != 0,
+            is_unique: field_to_u8(row.field(ITEM_IS_UNIQUE)?)? != 0,
+            is_untradable: field_to_u8(row.field(ITEM_IS_UNTRADABLE)?)? != 0,
+            can_be_hq: field_to_u8(row.field(ITEM_CAN_BE_HQ)?)? != 0,
+            dye_count: field_to_u8(row.field(ITEM_DYE_COUNT)?)?,
+            is_crest_worthy: field_to_u8(row.field(ITEM_IS_CREST_WORTHY)?)? != 0,
 
             ..Default::default()
         })
@@ -169,21 +339,77 @@ impl DataProvider for IronworksProvider {
         let items = excel.sheet("Materia")?;
         let row = items.row(id)?;
 
-        let mut item_id_vec = Vec::with_capacity(16);
-        let mut base_param_value_vec = Vec::with_capacity(16);
-        for i in 0..16 {
-            item_id_vec.push(field_to_u32(row.field(i)?)?);
-            base_param_value_vec.push(field_to_i16(row.field(i + 17)?)?);
-        }
+        materia_from_row(id, &row)
+    }
+
+    fn find_materia_by_item_id(&self, item_id: u32) -> Result<(u32, u8), DataProviderError> {
+        let materia_by_item = match self.materia_by_item.get() {
+            Some(materia_by_item) => materia_by_item,
+            None => {
+                let materia_by_item = self.load_materia_by_item()?;
+                self.materia_by_item.get_or_init(|| materia_by_item)
+            }
+        };
+
+        materia_by_item
+            .get(&item_id)
+            .copied()
+            .ok_or(DataProviderError::ObjectNotFound("no materia for item"))
+    }
 
-        Ok(Materia {
-            id: id,
-            item_id: item_id_vec,
-            base_param_id: field_to_i32(row.field(16)?)?,
-            base_param_value: base_param_value_vec,
+    fn class_job_category_includes(
+        &self,
+        category: u32,
+        class_job: u8,
+    ) -> Result<bool, DataProviderError> {
+        let excel =
+            Excel::new(Arc::clone(&self.ironworks)).with_default_language(Language::English);
+        let row = excel.sheet("ClassJobCategory")?.row(category)?;
+
+        // The first column is the category's name, then there's one per ClassJob.
+        Ok(field_to_u8(row.field(class_job as usize + 1)?)? != 0)
+    }
+
+    fn get_stain(&self, id: u32) -> Result<Stain, DataProviderError> {
+        let excel =
+            Excel::new(Arc::clone(&self.ironworks)).with_default_language(Language::English);
+        let row = excel.sheet("Stain")?.row(id)?;
+
+        Ok(Stain {
+            name: field_to_string(row.field(STAIN_NAME)?)?,
+            color: field_to_u32(row.field(STAIN_COLOR)?)?,
         })
     }
 
+    fn get_class_job_category_name(&self, category: u32) -> Result<String, DataProviderError> {
+        let excel =
+            Excel::new(Arc::clone(&self.ironworks)).with_default_language(Language::English);
+        let row = excel.sheet("ClassJobCategory")?.row(category)?;
+
+        field_to_string(row.field(0)?)
+    }
+
+    fn get_action(&self, id: u32) -> Result<Action, DataProviderError> {
+        self.get_named_icon("Action", id, ACTION_NAME, ACTION_ICON)
+    }
+
+    fn get_general_action(&self, id: u32) -> Result<Action, DataProviderError> {
+        self.get_named_icon(
+            "GeneralAction",
+            id,
+            GENERAL_ACTION_NAME,
+            GENERAL_ACTION_ICON,
+        )
+    }
+
+    fn get_emote(&self, id: u32) -> Result<Action, DataProviderError> {
+        self.get_named_icon("Emote", id, EMOTE_NAME, EMOTE_ICON)
+    }
+
+    fn get_craft_action(&self, id: u32) -> Result<Action, DataProviderError> {
+        self.get_named_icon("CraftAction", id, CRAFT_ACTION_NAME, CRAFT_ACTION_ICON)
+    }
+
     fn get_image(&self, path: &str) -> Result<ImageSource<'_>, DataProviderError> {
         // TODO: it would be nice if ironworks had a method to check for file existance before we return a uri
         Ok(ImageSource::Uri(format!("asset://{}", path).into()))
@@ -192,4 +418,14 @@ impl DataProvider for IronworksProvider {
     fn get_ui_image_by_id(&self, id: u32) -> Result<ImageSource<'_>, DataProviderError> {
         self.get_image(&ui_icon_path(id))
     }
+
+    fn get_texture(&self, path: &str) -> Result<RgbaImage, DataProviderError> {
+        decode_texture(&self.ironworks, path)
+            .map(|image| image.into_rgba8())
+            .map_err(|_| DataProviderError::ImageDecodeError("unable to decode texture"))
+    }
+
+    fn get_ui_texture_by_id(&self, id: u32) -> Result<RgbaImage, DataProviderError> {
+        self.get_texture(&ui_icon_path(id))
+    }
 }
@@ -0,0 +1,146 @@
+use libxivdat::dat_file::write_content;
+use libxivdat::section::{read_section_content, Section};
+
+use crate::gearset_file::backup_file;
+
+use std::path::{Path, PathBuf};
+
+// KEYBIND.DAT is a run of sections: a 'T' section naming a command, then a 'C' section holding
+// its bindings as "primary,secondary", each of which is "<key>.<modifiers>" in hex.
+const COMMAND_TAG: char = 'T';
+const BINDING_TAG: char = 'C';
+
+// Modifier bits, as the game stores them.
+const MODIFIER_SHIFT: u8 = 1;
+const MODIFIER_CTRL: u8 = 2;
+const MODIFIER_ALT: u8 = 4;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Keybind {
+    pub command: String,
+    pub primary: String,
+    pub secondary: String,
+}
+
+fn section_text(section: &Section) -> &str {
+    section.content.trim_end_matches('\0')
+}
+
+fn split_bindings(value: &str) -> (String, String) {
+    let (primary, secondary) = value.split_once(',').unwrap_or((value, ""));
+    (primary.to_string(), secondary.to_string())
+}
+
+/// Reads a KEYBIND.DAT.
+pub fn load_keybinds(path: &Path) -> Result<Vec<Keybind>, String> {
+    let sections =
+        read_section_content(path).map_err(|err| format!("unable to read {:?}: {}", path, err))?;
+
+    let mut keybinds = Vec::new();
+    let mut command = None;
+    for section in sections.iter() {
+        match section.tag {
+            COMMAND_TAG => command = Some(section_text(section).to_string()),
+            BINDING_TAG => {
+                if let Some(command) = command.take() {
+                    let (primary, secondary) = split_bindings(section_text(section));
+                    keybinds.push(Keybind {
+                        command,
+                        primary,
+                        secondary,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(keybinds)
+}
+
+/// Writes new bindings for the given commands over a KEYBIND.DAT, after backing it up.
+///
+/// Commands that aren't already in the file are left out; the game owns the list of commands.
+/// Returns the path of the backup.
+pub fn save_keybinds(path: &Path, keybinds: &[Keybind]) -> Result<PathBuf, String> {
+    let mut sections =
+        read_section_content(path).map_err(|err| format!("unable to read {:?}: {}", path, err))?;
+
+    let mut command = None;
+    for section in sections.iter_mut() {
+        match section.tag {
+            COMMAND_TAG => command = Some(section_text(section).to_string()),
+            BINDING_TAG => {
+                let Some(keybind) = command
+                    .take()
+                    .and_then(|command| keybinds.iter().find(|k| k.command == command))
+                else {
+                    continue;
+                };
+
+                let value = format!("{},{}\0", keybind.primary, keybind.secondary);
+                *section = Section::new(value, BINDING_TAG)
+                    .map_err(|err| format!("unable to encode {}: {}", keybind.command, err))?;
+            }
+            _ => {}
+        }
+    }
+
+    let bytes: Vec<u8> = sections.iter().flat_map(Vec::<u8>::from).collect();
+
+    let backup_path =
+        backup_file(path).map_err(|err| format!("unable to back up {:?}: {}", path, err))?;
+    write_content(path, &bytes).map_err(|err| format!("unable to write {:?}: {}", path, err))?;
+
+    Ok(backup_path)
+}
+
+fn key_name(key: u8) -> String {
+    match key {
+        0x08 => "Backspace".to_string(),
+        0x09 => "Tab".to_string(),
+        0x0D => "Enter".to_string(),
+        0x1B => "Esc".to_string(),
+        0x20 => "Space".to_string(),
+        0x21 => "Page Up".to_string(),
+        0x22 => "Page Down".to_string(),
+        0x23 => "End".to_string(),
+        0x24 => "Home".to_string(),
+        0x25 => "Left".to_string(),
+        0x26 => "Up".to_string(),
+        0x27 => "Right".to_string(),
+        0x28 => "Down".to_string(),
+        0x2D => "Insert".to_string(),
+        0x2E => "Delete".to_string(),
+        0x30..=0x39 | 0x41..=0x5A => (key as char).to_string(),
+        0x60..=0x69 => format!("Num {}", key - 0x60),
+        0x70..=0x87 => format!("F{}", key - 0x6F),
+        _ => format!("0x{:02X}", key),
+    }
+}
+
+/// Turns a stored binding like "57.2" into something readable like "Ctrl+W".
+pub fn display_binding(binding: &str) -> String {
+    let mut parts = binding
+        .split('.')
+        .map(|part| u8::from_str_radix(part, 16).unwrap_or(0));
+    let key = parts.next().unwrap_or(0);
+    if key == 0 {
+        return String::new();
+    }
+    let modifiers = parts.next().unwrap_or(0);
+
+    let mut out = String::new();
+    if modifiers & MODIFIER_CTRL != 0 {
+        out.push_str("Ctrl+");
+    }
+    if modifiers & MODIFIER_ALT != 0 {
+        out.push_str("Alt+");
+    }
+    if modifiers & MODIFIER_SHIFT != 0 {
+        out.push_str("Shift+");
+    }
+    out.push_str(&key_name(key));
+
+    out
+}
@@ -2,11 +2,26 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
 mod app;
+mod conformance;
 mod data_provider;
 mod directories;
+#[cfg(target_arch = "wasm32")]
+mod download;
 mod exporters;
+#[cfg(not(target_arch = "wasm32"))]
+mod file_watcher;
+mod gearset_file;
+mod hotbar_file;
+mod importers;
+mod item_order_file;
 mod iw_provider;
+mod keybind_file;
 mod model;
+mod snapshots;
+mod stats;
+mod timestamp;
+mod validation;
+mod views;
 
 use egui_extras;
 
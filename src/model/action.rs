@@ -0,0 +1,7 @@
+/// Something that can be put on a hotbar: an action, a general action or an emote.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Action {
+    pub id: u32,
+    pub name: String,
+    pub icon: String,
+}
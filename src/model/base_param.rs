@@ -0,0 +1,48 @@
+// Row ids in the BaseParam sheet for the stats we care about.
+pub const STRENGTH: i32 = 1;
+pub const DEXTERITY: i32 = 2;
+pub const VITALITY: i32 = 3;
+pub const INTELLIGENCE: i32 = 4;
+pub const MIND: i32 = 5;
+pub const PIETY: i32 = 6;
+pub const GP: i32 = 10;
+pub const CP: i32 = 11;
+pub const TENACITY: i32 = 19;
+pub const DIRECT_HIT: i32 = 22;
+pub const CRITICAL_HIT: i32 = 27;
+pub const DETERMINATION: i32 = 44;
+pub const SKILL_SPEED: i32 = 45;
+pub const SPELL_SPEED: i32 = 46;
+pub const CRAFTSMANSHIP: i32 = 70;
+pub const CONTROL: i32 = 71;
+pub const GATHERING: i32 = 72;
+pub const PERCEPTION: i32 = 73;
+
+/// Stats shown for Disciples of the Hand, in in-game order.
+pub const CRAFTER_PARAMS: [i32; 3] = [CRAFTSMANSHIP, CONTROL, CP];
+/// Stats shown for Disciples of the Land, in in-game order.
+pub const GATHERER_PARAMS: [i32; 3] = [GATHERING, PERCEPTION, GP];
+
+pub fn base_param_display_name(param: i32) -> &'static str {
+    match param {
+        STRENGTH => "Strength",
+        DEXTERITY => "Dexterity",
+        VITALITY => "Vitality",
+        INTELLIGENCE => "Intelligence",
+        MIND => "Mind",
+        PIETY => "Piety",
+        GP => "GP",
+        CP => "CP",
+        TENACITY => "Tenacity",
+        DIRECT_HIT => "Direct Hit Rate",
+        CRITICAL_HIT => "Critical Hit",
+        DETERMINATION => "Determination",
+        SKILL_SPEED => "Skill Speed",
+        SPELL_SPEED => "Spell Speed",
+        CRAFTSMANSHIP => "Craftsmanship",
+        CONTROL => "Control",
+        GATHERING => "Gathering",
+        PERCEPTION => "Perception",
+        _ => "",
+    }
+}
@@ -0,0 +1,174 @@
+pub const CLASSJOB_NAMES: [&str; 43] = [
+    "ADV", "GLA", "PGL", "MRD", "LNC", "ARC", "CNJ", "THM", "CRP", "BSM", "ARM", "GSM", "LTW",
+    "WVR", "ALC", "CUL", "MIN", "BTN", "FSH", "PLD", "MNK", "WAR", "DRG", "BRD", "WHM", "BLM",
+    "ACN", "SMN", "SCH", "ROG", "NIN", "MCH", "DRK", "AST", "SAM", "RDM", "BLU", "GNB", "DNC",
+    "RPR", "SGE", "VPR", "PCT",
+];
+
+// Spelled out for screen readers, which would otherwise read the abbreviations letter by letter.
+const CLASSJOB_FULL_NAMES: [&str; 43] = [
+    "Adventurer",
+    "Gladiator",
+    "Pugilist",
+    "Marauder",
+    "Lancer",
+    "Archer",
+    "Conjurer",
+    "Thaumaturge",
+    "Carpenter",
+    "Blacksmith",
+    "Armorer",
+    "Goldsmith",
+    "Leatherworker",
+    "Weaver",
+    "Alchemist",
+    "Culinarian",
+    "Miner",
+    "Botanist",
+    "Fisher",
+    "Paladin",
+    "Monk",
+    "Warrior",
+    "Dragoon",
+    "Bard",
+    "White Mage",
+    "Black Mage",
+    "Arcanist",
+    "Summoner",
+    "Scholar",
+    "Rogue",
+    "Ninja",
+    "Machinist",
+    "Dark Knight",
+    "Astrologian",
+    "Samurai",
+    "Red Mage",
+    "Blue Mage",
+    "Gunbreaker",
+    "Dancer",
+    "Reaper",
+    "Sage",
+    "Viper",
+    "Pictomancer",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClassJobKind {
+    /// Disciples of War and Magic
+    Combat,
+    /// Disciples of the Hand
+    Crafter,
+    /// Disciples of the Land
+    Gatherer,
+}
+
+// ClassJob rows 8-15 are CRP through CUL, and 16-18 are MIN, BTN and FSH. Every job added since
+// has been a combat job, so this is unlikely to need to change.
+pub fn class_job_kind(class_job: u8) -> ClassJobKind {
+    match class_job {
+        8..=15 => ClassJobKind::Crafter,
+        16..=18 => ClassJobKind::Gatherer,
+        _ => ClassJobKind::Combat,
+    }
+}
+
+/// What a class or job does in a party, or which kind of non-combat job it is.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Role {
+    Tank,
+    Healer,
+    Melee,
+    PhysicalRanged,
+    Caster,
+    Crafter,
+    Gatherer,
+}
+
+impl Role {
+    pub const ALL: [Role; 7] = [
+        Role::Tank,
+        Role::Healer,
+        Role::Melee,
+        Role::PhysicalRanged,
+        Role::Caster,
+        Role::Crafter,
+        Role::Gatherer,
+    ];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Role::Tank => "Tank",
+            Role::Healer => "Healer",
+            Role::Melee => "Melee DPS",
+            Role::PhysicalRanged => "Physical Ranged DPS",
+            Role::Caster => "Magical Ranged DPS",
+            Role::Crafter => "Disciple of the Hand",
+            Role::Gatherer => "Disciple of the Land",
+        }
+    }
+}
+
+// Base classes share their job's role. Adventurer (0) doesn't have one.
+pub fn class_job_role(class_job: u8) -> Option<Role> {
+    match promote_to_job(class_job) {
+        19 | 21 | 32 | 37 => Some(Role::Tank),
+        24 | 28 | 33 | 40 => Some(Role::Healer),
+        20 | 22 | 30 | 34 | 39 | 41 => Some(Role::Melee),
+        23 | 31 | 38 => Some(Role::PhysicalRanged),
+        25 | 27 | 35 | 36 | 42 => Some(Role::Caster),
+        8..=15 => Some(Role::Crafter),
+        16..=18 => Some(Role::Gatherer),
+        _ => None,
+    }
+}
+
+// The highest level a class or job can reach. Limited jobs (just BLU so far) lag behind.
+pub fn class_job_level_cap(class_job: u8) -> u8 {
+    match class_job {
+        36 => 80,
+        _ => 100,
+    }
+}
+
+pub fn class_job_abbreviation(class_job: u8) -> &'static str {
+    CLASSJOB_NAMES
+        .get(class_job as usize)
+        .copied()
+        .unwrap_or("???")
+}
+
+pub fn class_job_name(class_job: u8) -> &'static str {
+    CLASSJOB_FULL_NAMES
+        .get(class_job as usize)
+        .copied()
+        .unwrap_or("Unknown class")
+}
+
+// Maps a base class to the job it turns into.
+//
+// this information doesn't seem to be in the ClassJob table anywhere, but thankfully FFXIV will
+// never get a job with a different base class ever again, so hardcoding this table is fine.
+pub fn promote_to_job(class_job: u8) -> u8 {
+    match class_job {
+        // GLA -> PLD
+        1 => 19,
+        // PGL -> MNK
+        2 => 20,
+        // MRD -> WAR
+        3 => 21,
+        // LNC -> DRG
+        4 => 22,
+        // ARC -> BRD
+        5 => 23,
+        // CNJ -> WHM
+        6 => 24,
+        // THM -> BLM
+        7 => 25,
+        // ACN -> SMN
+        26 => 27,
+        // ROG -> NIN
+        29 => 30,
+        // all others
+        x => x,
+    }
+}
@@ -0,0 +1,147 @@
+pub const EQUIPMENT_SLOT_COUNT: usize = 14;
+
+/// One of the 14 equipment slots of a gearset, in the order GEARSET.DAT stores them.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum EquipSlot {
+    MainHand,
+    OffHand,
+    Head,
+    Body,
+    Hands,
+    // Belts were removed in Endwalker, but the slot is still there.
+    Waist,
+    Legs,
+    Feet,
+    Ears,
+    Neck,
+    Wrists,
+    LeftRing,
+    RightRing,
+    SoulCrystal,
+}
+
+impl EquipSlot {
+    pub const ALL: [EquipSlot; EQUIPMENT_SLOT_COUNT] = [
+        EquipSlot::MainHand,
+        EquipSlot::OffHand,
+        EquipSlot::Head,
+        EquipSlot::Body,
+        EquipSlot::Hands,
+        EquipSlot::Waist,
+        EquipSlot::Legs,
+        EquipSlot::Feet,
+        EquipSlot::Ears,
+        EquipSlot::Neck,
+        EquipSlot::Wrists,
+        EquipSlot::LeftRing,
+        EquipSlot::RightRing,
+        EquipSlot::SoulCrystal,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EquipSlot::MainHand => "Primary",
+            EquipSlot::OffHand => "Offhand",
+            EquipSlot::Head => "Head",
+            EquipSlot::Body => "Body",
+            EquipSlot::Hands => "Hand",
+            EquipSlot::Waist => "Belt",
+            EquipSlot::Legs => "Legs",
+            EquipSlot::Feet => "Feet",
+            EquipSlot::Ears => "Earrings",
+            EquipSlot::Neck => "Necklace",
+            EquipSlot::Wrists => "Wrist",
+            EquipSlot::LeftRing => "Left Ring",
+            EquipSlot::RightRing => "Right Ring",
+            EquipSlot::SoulCrystal => "Soul Crystal",
+        }
+    }
+
+    /// The key xivgear uses for this slot, if it has one. xivgear has no belts, and leaves soul
+    /// crystals out since the job already says which one is worn.
+    pub fn xivgear_label(self) -> Option<&'static str> {
+        match self {
+            EquipSlot::MainHand => Some("Weapon"),
+            EquipSlot::OffHand => Some("OffHand"),
+            EquipSlot::Head => Some("Head"),
+            EquipSlot::Body => Some("Body"),
+            EquipSlot::Hands => Some("Hand"),
+            EquipSlot::Waist => None,
+            EquipSlot::Legs => Some("Legs"),
+            EquipSlot::Feet => Some("Feet"),
+            EquipSlot::Ears => Some("Ears"),
+            EquipSlot::Neck => Some("Neck"),
+            EquipSlot::Wrists => Some("Wrist"),
+            EquipSlot::LeftRing => Some("RingLeft"),
+            EquipSlot::RightRing => Some("RingRight"),
+            EquipSlot::SoulCrystal => None,
+        }
+    }
+
+    /// The key Teamcraft uses for this slot.
+    pub fn teamcraft_label(self) -> &'static str {
+        match self {
+            EquipSlot::MainHand => "mainHand",
+            EquipSlot::OffHand => "offHand",
+            EquipSlot::Head => "head",
+            EquipSlot::Body => "chest",
+            EquipSlot::Hands => "gloves",
+            EquipSlot::Waist => "belt",
+            EquipSlot::Legs => "legs",
+            EquipSlot::Feet => "feet",
+            EquipSlot::Ears => "earRings",
+            EquipSlot::Neck => "necklace",
+            EquipSlot::Wrists => "bracelet",
+            EquipSlot::LeftRing => "ring1",
+            EquipSlot::RightRing => "ring2",
+            EquipSlot::SoulCrystal => "crystal",
+        }
+    }
+
+    /// The key Etro uses for this slot, if it has one, and the suffix it puts on the item id in
+    /// its materia map (rings need one, since both slots could hold the same item).
+    pub fn etro_label(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            EquipSlot::MainHand => Some(("weapon", "")),
+            EquipSlot::OffHand => Some(("offHand", "")),
+            EquipSlot::Head => Some(("head", "")),
+            EquipSlot::Body => Some(("body", "")),
+            EquipSlot::Hands => Some(("hands", "")),
+            EquipSlot::Waist => None,
+            EquipSlot::Legs => Some(("legs", "")),
+            EquipSlot::Feet => Some(("feet", "")),
+            EquipSlot::Ears => Some(("ears", "")),
+            EquipSlot::Neck => Some(("neck", "")),
+            EquipSlot::Wrists => Some(("wrists", "")),
+            EquipSlot::LeftRing => Some(("fingerL", "L")),
+            EquipSlot::RightRing => Some(("fingerR", "R")),
+            EquipSlot::SoulCrystal => None,
+        }
+    }
+
+    /// Whether an item of the given EquipSlotCategory can go in this slot.
+    ///
+    /// Items that cover several slots (two-handed weapons, full-body outfits) are equipped in
+    /// the main one and lock the others.
+    pub fn accepts(self, equip_slot_category: u8) -> bool {
+        match self {
+            EquipSlot::MainHand => matches!(equip_slot_category, 1 | 13 | 14),
+            EquipSlot::OffHand => equip_slot_category == 2,
+            EquipSlot::Head => equip_slot_category == 3,
+            EquipSlot::Body => matches!(equip_slot_category, 4 | 15 | 16 | 19 | 20 | 21 | 22),
+            EquipSlot::Hands => equip_slot_category == 5,
+            EquipSlot::Waist => equip_slot_category == 6,
+            EquipSlot::Legs => matches!(equip_slot_category, 7 | 18),
+            EquipSlot::Feet => equip_slot_category == 8,
+            EquipSlot::Ears => equip_slot_category == 9,
+            EquipSlot::Neck => equip_slot_category == 10,
+            EquipSlot::Wrists => equip_slot_category == 11,
+            EquipSlot::LeftRing | EquipSlot::RightRing => equip_slot_category == 12,
+            EquipSlot::SoulCrystal => equip_slot_category == 17,
+        }
+    }
+}
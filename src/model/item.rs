@@ -1,13 +1,14 @@
+use crate::model::ItemId;
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Item {
-    pub id: u32,
+    pub id: ItemId,
     pub name: String,
     pub description: String,
     pub icon: String,
 
     // weapon type
-    // item level
-    //pub level_item,
+    pub level_item: u16,
     // rarity
 
     // phys damage (DamagePhys)
@@ -23,11 +24,20 @@ pub struct Item {
     // auto-attack time
     // delay
 
-    // classes
+    // which classes can equip it; see DataProvider::class_job_category_includes
+    pub class_job_category: u32,
     // min lvl requirement
     pub level_equip: u8,
+    // which slots it goes in; see EquipSlot::accepts
+    pub equip_slot_category: u8,
 
     // stat bonuses
+    pub base_param_ids: Vec<i32>,
+    pub base_param_values: Vec<i16>,
+    // additional stat bonuses applied when HQ
+    pub base_param_special_ids: Vec<i32>,
+    pub base_param_special_values: Vec<i16>,
+
     pub materia_slot_count: u8,
     pub advanced_melds_permitted: bool,
 
@@ -0,0 +1,116 @@
+use std::fmt;
+
+// Quality is folded into item ids by offsetting them, in GEARSET.DAT and everywhere else.
+const COLLECTABLE_OFFSET: u32 = 500000;
+const HQ_OFFSET: u32 = 1000000;
+// Key items live in the EventItem sheet, whose rows start here.
+const EVENT_ITEM_START: u32 = 2000000;
+
+/// How an [`ItemId`] should be read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ItemKind {
+    Normal,
+    HighQuality,
+    Collectable,
+    /// A key item, from the EventItem sheet rather than Item.
+    Event,
+}
+
+/// An item id as the game stores it, with the item's quality folded in.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ItemId(pub u32);
+
+impl ItemId {
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn kind(&self) -> ItemKind {
+        match self.0 {
+            x if x >= EVENT_ITEM_START => ItemKind::Event,
+            x if x >= HQ_OFFSET => ItemKind::HighQuality,
+            x if x >= COLLECTABLE_OFFSET => ItemKind::Collectable,
+            _ => ItemKind::Normal,
+        }
+    }
+
+    pub fn is_hq(&self) -> bool {
+        self.kind() == ItemKind::HighQuality
+    }
+
+    /// The row in the Item (or EventItem) sheet, regardless of quality.
+    pub fn base_id(&self) -> u32 {
+        match self.kind() {
+            ItemKind::HighQuality => self.0 - HQ_OFFSET,
+            ItemKind::Collectable => self.0 - COLLECTABLE_OFFSET,
+            ItemKind::Normal | ItemKind::Event => self.0,
+        }
+    }
+
+    /// The same item without any quality folded in.
+    pub fn base(&self) -> ItemId {
+        ItemId(self.base_id())
+    }
+}
+
+impl From<u32> for ItemId {
+    fn from(id: u32) -> Self {
+        ItemId(id)
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_items_are_their_own_base() {
+        let id = ItemId(40000);
+        assert_eq!(id.kind(), ItemKind::Normal);
+        assert!(!id.is_hq());
+        assert_eq!(id.base(), id);
+    }
+
+    #[test]
+    fn hq_offset_is_folded_out() {
+        let id = ItemId(1040000);
+        assert_eq!(id.kind(), ItemKind::HighQuality);
+        assert!(id.is_hq());
+        assert_eq!(id.base_id(), 40000);
+    }
+
+    #[test]
+    fn collectable_offset_is_folded_out() {
+        let id = ItemId(540000);
+        assert_eq!(id.kind(), ItemKind::Collectable);
+        assert!(!id.is_hq());
+        assert_eq!(id.base_id(), 40000);
+    }
+
+    #[test]
+    fn event_items_keep_their_id() {
+        let id = ItemId(2000123);
+        assert_eq!(id.kind(), ItemKind::Event);
+        assert_eq!(id.base_id(), 2000123);
+    }
+
+    #[test]
+    fn offsets_start_exactly_at_their_boundaries() {
+        assert_eq!(ItemId(HQ_OFFSET - 1).kind(), ItemKind::Collectable);
+        assert_eq!(ItemId(HQ_OFFSET).kind(), ItemKind::HighQuality);
+        assert_eq!(ItemId(COLLECTABLE_OFFSET - 1).kind(), ItemKind::Normal);
+        assert_eq!(ItemId(EVENT_ITEM_START - 1).kind(), ItemKind::HighQuality);
+    }
+
+    #[test]
+    fn zero_is_empty() {
+        assert!(ItemId(0).is_empty());
+        assert!(!ItemId(1).is_empty());
+    }
+}
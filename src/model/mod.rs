@@ -1,7 +1,23 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+mod action;
+pub mod base_param;
+mod class_job;
+mod equip_slot;
 mod item;
+mod item_id;
 mod materia;
+mod portable_gearset;
+mod stain;
 
+pub use action::Action;
+pub use class_job::{
+    class_job_abbreviation, class_job_kind, class_job_level_cap, class_job_name, class_job_role,
+    promote_to_job, ClassJobKind, Role, CLASSJOB_NAMES,
+};
+pub use equip_slot::{EquipSlot, EQUIPMENT_SLOT_COUNT};
 pub use item::Item;
+pub use item_id::{ItemId, ItemKind};
 pub use materia::Materia;
+pub use portable_gearset::{PortableGearset, PortableItem};
+pub use stain::Stain;
@@ -0,0 +1,72 @@
+use libxivdat::xiv_gearset::Gearset;
+
+use crate::model::ItemId;
+#[cfg(test)]
+use crate::model::{EquipSlot, EQUIPMENT_SLOT_COUNT};
+
+/// One equipment slot of a [`PortableGearset`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PortableItem {
+    pub item_id: ItemId,
+    pub materia_types: Vec<u16>,
+    pub materia_grades: Vec<u8>,
+}
+
+impl PortableItem {
+    /// The materia melded into this item, as (type, grade), skipping empty slots.
+    pub fn melds(&self) -> Vec<(u16, u8)> {
+        self.materia_types
+            .iter()
+            .zip(self.materia_grades.iter())
+            .filter(|(t, _)| **t != 0)
+            .map(|(t, g)| (*t, *g))
+            .collect()
+    }
+}
+
+/// A gearset that doesn't necessarily live in a GEARSET.DAT, such as one that was imported
+/// from a share code. The equipment is laid out in the same 14 slots as a `Gearset`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PortableGearset {
+    pub name: String,
+    pub class_job: u8,
+    pub equipment: Vec<PortableItem>,
+}
+
+impl From<&Gearset> for PortableGearset {
+    fn from(gearset: &Gearset) -> PortableGearset {
+        PortableGearset {
+            name: gearset.name.clone(),
+            class_job: gearset.class_job,
+            equipment: gearset
+                .equipment
+                .iter()
+                .map(|eq| PortableItem {
+                    item_id: ItemId(eq.item_id),
+                    materia_types: eq.materia_types.iter().map(|t| *t as u16).collect(),
+                    materia_grades: eq.materia_grades.iter().map(|g| *g as u8).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl PortableGearset {
+    /// A machinist set holding the given items, each with its melds as (type, grade).
+    pub fn with_items(items: &[(EquipSlot, u32, &[(u16, u8)])]) -> PortableGearset {
+        let mut equipment = vec![PortableItem::default(); EQUIPMENT_SLOT_COUNT];
+        for (slot, item_id, melds) in items {
+            equipment[slot.index()] = PortableItem {
+                item_id: ItemId(*item_id),
+                materia_types: melds.iter().map(|(t, _)| *t).collect(),
+                materia_grades: melds.iter().map(|(_, g)| *g).collect(),
+            };
+        }
+        PortableGearset {
+            name: "Test".to_string(),
+            class_job: 31,
+            equipment,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/// A dye, as it appears in the Stain sheet.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stain {
+    pub name: String,
+    // 0xRRGGBB
+    pub color: u32,
+}
@@ -0,0 +1,261 @@
+use libxivdat::xiv_gearset::{Gearset, GearsetFlags, GearsetList};
+
+use crate::model::PortableGearset;
+use crate::timestamp::file_timestamp;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How often to look for changed DAT files.
+#[cfg(not(target_arch = "wasm32"))]
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Where snapshots are kept: `<app storage>/history/<character folder>/<DAT name>/<time>.DAT`.
+fn history_root() -> Option<PathBuf> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        eframe::storage_dir("roarich").map(|dir| dir.join("history"))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+/// The character folder (e.g. `FFXIV_CHR004000000000000`) that a DAT file lives in.
+pub fn character_folder(dat_path: &Path) -> Option<String> {
+    dat_path
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn history_dir(dat_path: &Path) -> Option<PathBuf> {
+    let character = character_folder(dat_path)?;
+    let file_name = dat_path.file_name()?;
+
+    Some(history_root()?.join(character).join(file_name))
+}
+
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub taken: SystemTime,
+}
+
+/// Lists the snapshots of a DAT file, newest first.
+pub fn list_snapshots(dat_path: &Path) -> Vec<Snapshot> {
+    let Some(dir) = history_dir(dat_path) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut snapshots: Vec<Snapshot> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "DAT"))
+        .map(|path| Snapshot {
+            taken: std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH),
+            path,
+        })
+        .collect();
+
+    // File names are timestamps, so they sort chronologically.
+    snapshots.sort_by(|a, b| b.path.cmp(&a.path));
+    snapshots
+}
+
+/// Copies a DAT file into its history folder, unless the newest snapshot is already identical.
+/// Returns the path of the new snapshot, if one was taken.
+pub fn snapshot_if_changed(dat_path: &Path) -> std::io::Result<Option<PathBuf>> {
+    let Some(dir) = history_dir(dat_path) else {
+        return Ok(None);
+    };
+
+    let contents = std::fs::read(dat_path)?;
+    if let Some(latest) = list_snapshots(dat_path).first() {
+        if std::fs::read(&latest.path).is_ok_and(|latest_contents| latest_contents == contents) {
+            return Ok(None);
+        }
+    }
+
+    std::fs::create_dir_all(&dir)?;
+    // The game can write a file more than once a second (saving several things on logout, say),
+    // so the milliseconds keep those snapshots from replacing each other.
+    let now = SystemTime::now();
+    let millis = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_millis());
+    let snapshot_path = dir.join(format!("{}-{:03}.DAT", file_timestamp(now), millis));
+    std::fs::write(&snapshot_path, contents)?;
+
+    Ok(Some(snapshot_path))
+}
+
+/// Keeps an eye on every DAT file in a character folder, snapshotting the ones that change.
+///
+/// The checking happens on a background thread, so the UI never waits on the disk.
+#[derive(Default)]
+pub struct SnapshotWatcher {
+    character_dir: Option<PathBuf>,
+    // Dropping this tells the thread to stop.
+    stop: Option<mpsc::Sender<()>>,
+    // DAT files that were just snapshotted.
+    snapshotted: Option<mpsc::Receiver<PathBuf>>,
+}
+
+impl SnapshotWatcher {
+    /// Starts watching the character folder that `dat_path` is in.
+    pub fn watch(&mut self, dat_path: &Path) {
+        let character_dir = dat_path.parent().map(Path::to_path_buf);
+        if character_dir == self.character_dir {
+            return;
+        }
+        self.character_dir = character_dir.clone();
+        self.stop = None;
+        self.snapshotted = None;
+
+        // There's nowhere to keep snapshots on the web.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dir) = character_dir {
+            let (stop_tx, stop_rx) = mpsc::channel();
+            let (snapshotted_tx, snapshotted_rx) = mpsc::channel();
+            std::thread::spawn(move || watch_character_dir(&dir, &stop_rx, &snapshotted_tx));
+            self.stop = Some(stop_tx);
+            self.snapshotted = Some(snapshotted_rx);
+        }
+    }
+
+    /// Returns the DAT files that have been snapshotted since the last call.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        self.snapshotted
+            .as_ref()
+            .map(|snapshotted| snapshotted.try_iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn watch_character_dir(dir: &Path, stop: &mpsc::Receiver<()>, snapshotted: &mpsc::Sender<PathBuf>) {
+    let mut last_modified: HashMap<PathBuf, SystemTime> = HashMap::new();
+
+    loop {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+                if !path.extension().is_some_and(|ext| ext == "DAT") {
+                    continue;
+                }
+                let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                    continue;
+                };
+                if last_modified.get(&path) == Some(&modified) {
+                    continue;
+                }
+
+                match snapshot_if_changed(&path) {
+                    Ok(Some(snapshot_path)) => {
+                        log::info!("snapshotted {:?} to {:?}", path, snapshot_path);
+                        if snapshotted.send(path.clone()).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(err) => log::error!("unable to snapshot {:?}: {}", path, err),
+                }
+                last_modified.insert(path, modified);
+            }
+        }
+
+        // Stop as soon as the watcher goes away, rather than at the next check.
+        match stop.recv_timeout(CHECK_INTERVAL) {
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum GearsetDiff {
+    Added(u8),
+    Removed(u8),
+    Changed(u8, Vec<String>),
+}
+
+impl GearsetDiff {
+    pub fn set_number(&self) -> u8 {
+        match self {
+            GearsetDiff::Added(set_number)
+            | GearsetDiff::Removed(set_number)
+            | GearsetDiff::Changed(set_number, _) => *set_number,
+        }
+    }
+
+    /// A short description, like "#3 changed".
+    pub fn summary(&self) -> String {
+        match self {
+            GearsetDiff::Added(set_number) => format!("#{} added", set_number),
+            GearsetDiff::Removed(set_number) => format!("#{} removed", set_number),
+            GearsetDiff::Changed(set_number, _) => format!("#{} changed", set_number),
+        }
+    }
+}
+
+fn gearset_exists(gs: &Gearset) -> bool {
+    (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists
+}
+
+/// Compares two versions of a gearset list, set by set.
+pub fn diff_gearsets(old: &GearsetList, new: &GearsetList) -> Vec<GearsetDiff> {
+    let mut diffs = Vec::new();
+
+    for (old_gs, new_gs) in old.gearsets.iter().zip(new.gearsets.iter()) {
+        let set_number = new_gs.set_number as u8;
+
+        match (gearset_exists(old_gs), gearset_exists(new_gs)) {
+            (false, false) => {}
+            (false, true) => diffs.push(GearsetDiff::Added(set_number)),
+            (true, false) => diffs.push(GearsetDiff::Removed(set_number)),
+            (true, true) => {
+                let mut changes = Vec::new();
+                if old_gs.name != new_gs.name {
+                    changes.push(format!("renamed from \"{}\"", old_gs.name));
+                }
+                if old_gs.class_job != new_gs.class_job {
+                    changes.push("job changed".to_string());
+                }
+                if old_gs.glamour_plate != new_gs.glamour_plate {
+                    changes.push(format!(
+                        "glamour plate {} -> {}",
+                        old_gs.glamour_plate, new_gs.glamour_plate
+                    ));
+                }
+
+                let old_portable = PortableGearset::from(old_gs);
+                let new_portable = PortableGearset::from(new_gs);
+                let changed_slots = old_portable
+                    .equipment
+                    .iter()
+                    .zip(new_portable.equipment.iter())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                if changed_slots > 0 {
+                    changes.push(format!("{} slot(s) changed", changed_slots));
+                }
+
+                if !changes.is_empty() {
+                    diffs.push(GearsetDiff::Changed(set_number, changes));
+                }
+            }
+        }
+    }
+
+    diffs
+}
@@ -0,0 +1,74 @@
+use crate::data_provider::DataProvider;
+use crate::model::ItemId;
+use libxivdat::xiv_gearset::Gearset;
+use std::collections::BTreeMap;
+
+/// Looks up the stat and amount granted by a melded materia of the given type and grade.
+pub fn materia_stat<T: DataProvider>(
+    data_provider: &T,
+    materia_type: u32,
+    materia_grade: usize,
+) -> Option<(i32, i16)> {
+    let materia = data_provider.get_materia(materia_type).ok()?;
+    let value = *materia.base_param_value.get(materia_grade)?;
+
+    Some((materia.base_param_id, value))
+}
+
+/// Sums the stats granted by every item and melded materia in a gearset, keyed by BaseParam id.
+///
+/// This is only what the gear itself provides: the character's own base stats (such as the
+/// 180 CP every crafter starts with) aren't included, and materia aren't clamped to the
+/// per-item caps.
+pub fn gearset_stat_totals<T: DataProvider>(
+    gearset: &Gearset,
+    data_provider: &T,
+) -> BTreeMap<i32, i32> {
+    let mut totals = BTreeMap::new();
+
+    for eq in gearset.equipment.iter() {
+        let item_id = ItemId(eq.item_id);
+        if item_id.is_empty() {
+            continue;
+        }
+
+        let Ok(item) = data_provider.get_item(item_id) else {
+            continue;
+        };
+
+        for (param, value) in item
+            .base_param_ids
+            .iter()
+            .zip(item.base_param_values.iter())
+        {
+            *totals.entry(*param).or_insert(0) += *value as i32;
+        }
+
+        // HQ items get their special params added on top of the NQ ones.
+        if item_id.is_hq() {
+            for (param, value) in item
+                .base_param_special_ids
+                .iter()
+                .zip(item.base_param_special_values.iter())
+            {
+                *totals.entry(*param).or_insert(0) += *value as i32;
+            }
+        }
+
+        for m in 0..eq.materia_types.len() {
+            if eq.materia_types[m] == 0 {
+                continue;
+            }
+
+            if let Some((param, value)) = materia_stat(
+                data_provider,
+                eq.materia_types[m] as u32,
+                eq.materia_grades[m] as usize,
+            ) {
+                *totals.entry(param).or_insert(0) += value as i32;
+            }
+        }
+    }
+
+    totals
+}
@@ -0,0 +1,51 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Splits a time into UTC (year, month, day, hour, minute, second).
+//
+// We don't pull in chrono just for this; the date half is Howard Hinnant's civil_from_days.
+fn to_utc_parts(time: SystemTime) -> (i64, u32, u32, u32, u32, u32) {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    let days = secs.div_euclid(86400);
+    let secs_of_day = secs.rem_euclid(86400) as u32;
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day / 60) % 60,
+        secs_of_day % 60,
+    )
+}
+
+/// Formats a time for display, like "2025-02-14 18:30:05 UTC".
+pub fn display_timestamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, second) = to_utc_parts(time);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year, month, day, hour, minute, second
+    )
+}
+
+/// Formats a time for use in a file name, like "20250214-183005".
+pub fn file_timestamp(time: SystemTime) -> String {
+    let (year, month, day, hour, minute, second) = to_utc_parts(time);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year, month, day, hour, minute, second
+    )
+}
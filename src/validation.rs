@@ -0,0 +1,247 @@
+use crate::data_provider::DataProvider;
+use crate::model::{class_job_abbreviation, EquipSlot, ItemId, PortableGearset};
+
+use std::fmt;
+
+// EquipSlotCategory of two-handed weapons, which lock the off hand.
+const TWO_HANDED_CATEGORY: u8 = 13;
+// Advanced melding only goes up to five materia, however many slots the item has.
+const MAX_MATERIA: usize = 5;
+
+/// Something wrong with a gearset that the game would refuse, or that's otherwise suspicious.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GearsetProblem {
+    /// The item can't be equipped in the slot it's in.
+    WrongSlot(EquipSlot, String),
+    /// The item can't be equipped by the set's class or job.
+    WrongClassJob(EquipSlot, String, u8),
+    /// The item needs a higher level than the class or job is at.
+    LevelTooHigh(EquipSlot, String, u8),
+    /// There's something in the off hand, but the main hand is two-handed.
+    OffHandWithTwoHanded,
+    /// A unique item is equipped in both ring slots.
+    UniqueTwice(String),
+    /// More materia are melded than the item allows: its slots if it can't be overmelded, and
+    /// five if it can.
+    TooManyMateria(EquipSlot, String, usize, usize),
+    /// The item isn't in the game data (anymore).
+    MissingItem(EquipSlot, ItemId),
+}
+
+impl fmt::Display for GearsetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GearsetProblem::WrongSlot(slot, name) => {
+                write!(
+                    f,
+                    "{}: {} doesn't go in this slot",
+                    slot.display_name(),
+                    name
+                )
+            }
+            GearsetProblem::WrongClassJob(slot, name, class_job) => write!(
+                f,
+                "{}: {} can't be equipped by {}",
+                slot.display_name(),
+                name,
+                class_job_abbreviation(*class_job)
+            ),
+            GearsetProblem::LevelTooHigh(slot, name, level) => {
+                write!(f, "{}: {} needs level {}", slot.display_name(), name, level)
+            }
+            GearsetProblem::OffHandWithTwoHanded => {
+                write!(f, "Offhand: the main hand weapon is two-handed")
+            }
+            GearsetProblem::UniqueTwice(name) => {
+                write!(f, "Rings: {} is unique, but is equipped twice", name)
+            }
+            GearsetProblem::TooManyMateria(slot, name, melded, allowed) => write!(
+                f,
+                "{}: {} has {} materia but only room for {}",
+                slot.display_name(),
+                name,
+                melded,
+                allowed
+            ),
+            GearsetProblem::MissingItem(slot, item_id) => {
+                write!(f, "{}: item {} doesn't exist", slot.display_name(), item_id)
+            }
+        }
+    }
+}
+
+/// Checks every item in a gearset against the rules the game enforces when equipping it.
+///
+/// GEARSET.DAT doesn't record how far each job has been levelled, so the caller says what level
+/// to check equip levels against.
+pub fn validate_gearset<T: DataProvider>(
+    gearset: &PortableGearset,
+    level: u8,
+    data_provider: &T,
+) -> Vec<GearsetProblem> {
+    let mut problems = Vec::new();
+
+    let mut main_hand_category = 0;
+    let mut unique_rings = Vec::new();
+    for (slot, eq) in EquipSlot::ALL.iter().zip(gearset.equipment.iter()) {
+        let item_id = eq.item_id;
+        if item_id.is_empty() {
+            continue;
+        }
+
+        let Ok(item) = data_provider.get_item(item_id) else {
+            problems.push(GearsetProblem::MissingItem(*slot, item_id));
+            continue;
+        };
+
+        if !slot.accepts(item.equip_slot_category) {
+            problems.push(GearsetProblem::WrongSlot(*slot, item.name.clone()));
+        }
+
+        if let Ok(false) =
+            data_provider.class_job_category_includes(item.class_job_category, gearset.class_job)
+        {
+            problems.push(GearsetProblem::WrongClassJob(
+                *slot,
+                item.name.clone(),
+                gearset.class_job,
+            ));
+        }
+
+        if item.level_equip > level {
+            problems.push(GearsetProblem::LevelTooHigh(
+                *slot,
+                item.name.clone(),
+                item.level_equip,
+            ));
+        }
+
+        match slot {
+            EquipSlot::MainHand => main_hand_category = item.equip_slot_category,
+            EquipSlot::OffHand if main_hand_category == TWO_HANDED_CATEGORY => {
+                problems.push(GearsetProblem::OffHandWithTwoHanded);
+            }
+            EquipSlot::LeftRing | EquipSlot::RightRing if item.is_unique => {
+                if unique_rings.contains(&item_id.base()) {
+                    problems.push(GearsetProblem::UniqueTwice(item.name.clone()));
+                }
+                unique_rings.push(item_id.base());
+            }
+            _ => {}
+        }
+
+        // Relic stat bonuses are stored like materia but don't take up a slot, so only count
+        // the ones that are real materia items.
+        let melded = eq
+            .materia_types
+            .iter()
+            .zip(eq.materia_grades.iter())
+            .filter(|(t, g)| {
+                **t != 0
+                    && data_provider
+                        .get_materia(**t as u32)
+                        .ok()
+                        .and_then(|materia| materia.item_id.get(**g as usize).copied())
+                        .is_some_and(|materia_item_id| materia_item_id != 0)
+            })
+            .count();
+        let allowed = match item.advanced_melds_permitted {
+            true => MAX_MATERIA,
+            false => item.materia_slot_count as usize,
+        };
+        if melded > allowed {
+            problems.push(GearsetProblem::TooManyMateria(
+                *slot, item.name, melded, allowed,
+            ));
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_provider::test_provider::TestProvider;
+
+    const BODY: u32 = 40000;
+    const CRAFTED_BODY: u32 = 43000;
+    const RING: u32 = 45000;
+    const SAVAGE_AIM_XII: (u16, u8) = (20, 11);
+
+    #[test]
+    fn valid_set_has_no_problems() {
+        let gs = PortableGearset::with_items(&[
+            (EquipSlot::Body, BODY, &[SAVAGE_AIM_XII; 2]),
+            (EquipSlot::LeftRing, RING, &[]),
+        ]);
+        assert_eq!(validate_gearset(&gs, 90, &TestProvider), vec![]);
+    }
+
+    #[test]
+    fn checks_equip_level_against_the_given_level() {
+        let gs = PortableGearset::with_items(&[(EquipSlot::Body, BODY, &[])]);
+        assert_eq!(
+            validate_gearset(&gs, 89, &TestProvider),
+            vec![GearsetProblem::LevelTooHigh(
+                EquipSlot::Body,
+                String::new(),
+                90
+            )]
+        );
+    }
+
+    #[test]
+    fn item_in_the_wrong_slot() {
+        let gs = PortableGearset::with_items(&[(EquipSlot::Head, BODY, &[])]);
+        assert_eq!(
+            validate_gearset(&gs, 90, &TestProvider),
+            vec![GearsetProblem::WrongSlot(EquipSlot::Head, String::new())]
+        );
+    }
+
+    #[test]
+    fn unique_ring_twice_whatever_the_quality() {
+        let gs = PortableGearset::with_items(&[
+            (EquipSlot::LeftRing, RING, &[]),
+            (EquipSlot::RightRing, 1_000_000 + RING, &[]),
+        ]);
+        assert_eq!(
+            validate_gearset(&gs, 90, &TestProvider),
+            vec![GearsetProblem::UniqueTwice(String::new())]
+        );
+    }
+
+    #[test]
+    fn more_materia_than_slots() {
+        let gs = PortableGearset::with_items(&[(EquipSlot::Body, BODY, &[SAVAGE_AIM_XII; 3])]);
+        assert_eq!(
+            validate_gearset(&gs, 90, &TestProvider),
+            vec![GearsetProblem::TooManyMateria(
+                EquipSlot::Body,
+                String::new(),
+                3,
+                2
+            )]
+        );
+    }
+
+    #[test]
+    fn overmelds_stop_at_five() {
+        let gs =
+            PortableGearset::with_items(&[(EquipSlot::Body, CRAFTED_BODY, &[SAVAGE_AIM_XII; 5])]);
+        assert_eq!(validate_gearset(&gs, 90, &TestProvider), vec![]);
+
+        let gs =
+            PortableGearset::with_items(&[(EquipSlot::Body, CRAFTED_BODY, &[SAVAGE_AIM_XII; 6])]);
+        assert_eq!(
+            validate_gearset(&gs, 90, &TestProvider),
+            vec![GearsetProblem::TooManyMateria(
+                EquipSlot::Body,
+                String::new(),
+                6,
+                5
+            )]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::iw_provider::IronworksProvider;
+use crate::model::{EquipSlot, ItemId};
+
+use libxivdat::xiv_gearset::Gearset;
+
+fn show_stain(ui: &mut egui::Ui, data_provider: &IronworksProvider, stain_id: u8) {
+    if stain_id == 0 {
+        return;
+    }
+
+    let Ok(stain) = data_provider.get_stain(stain_id as u32) else {
+        ui.label(format!("Dye {}", stain_id));
+        return;
+    };
+
+    let [_, r, g, b] = stain.color.to_be_bytes();
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+    ui.painter()
+        .rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+    ui.label(stain.name);
+}
+
+/// Shows a gearset's glamour plate and the glamour and dyes of each slot.
+pub fn show_glamour(ui: &mut egui::Ui, data_provider: &IronworksProvider, gearset: &Gearset) {
+    use egui_extras::{Column, TableBuilder};
+
+    match gearset.glamour_plate {
+        0 => ui.label("No glamour plate"),
+        plate => ui.label(format!("Glamour plate {}", plate)),
+    };
+
+    let slots: Vec<_> = EquipSlot::ALL
+        .iter()
+        .zip(gearset.equipment.iter())
+        .filter(|(_, eq)| eq.glamour_id != 0 || eq.stains.iter().any(|s| *s != 0))
+        .collect();
+    if slots.is_empty() {
+        ui.weak("No slots are glamoured or dyed.");
+        return;
+    }
+
+    let table = TableBuilder::new(ui)
+        .id_salt("glamour")
+        .striped(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::remainder());
+
+    table.body(|mut body| {
+        for (slot, eq) in slots {
+            // Without a glamour, the dyes apply to the equipped item itself.
+            let shown_id = match eq.glamour_id {
+                0 => ItemId(eq.item_id),
+                glamour_id => ItemId(glamour_id),
+            };
+            let item = data_provider.get_item(shown_id);
+
+            body.row(32.0, |mut row| {
+                row.col(|ui| {
+                    ui.label(slot.display_name());
+                });
+                row.col(|ui| {
+                    if let Ok(item) = &item {
+                        if let Ok(img) = data_provider.get_image(&item.icon) {
+                            ui.add(egui::Image::new(img)).widget_info(|| {
+                                egui::WidgetInfo::labeled(egui::WidgetType::Image, true, &item.name)
+                            });
+                        }
+                    }
+                });
+                row.col(|ui| {
+                    let name = match &item {
+                        Ok(item) => item.name.clone(),
+                        Err(_) => format!("Unknown item {}", shown_id),
+                    };
+                    if eq.glamour_id == 0 {
+                        ui.weak(name);
+                    } else {
+                        ui.label(name);
+                    }
+                });
+                row.col(|ui| {
+                    for stain_id in eq.stains.iter() {
+                        show_stain(ui, data_provider, *stain_id);
+                    }
+                });
+            });
+        }
+    });
+}
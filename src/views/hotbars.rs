@@ -0,0 +1,291 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use libxivdat::xiv_macro::{read_macro_content, Macro};
+
+use super::macros::macro_icon_id;
+use crate::data_provider::DataProvider;
+use crate::hotbar_file::{
+    load_hotbars, HotbarFile, HotbarLayout, HotbarSlot, HotbarSlotKind, HOTBAR_COUNT,
+    HOTBAR_VISIBLE_SLOTS,
+};
+use crate::iw_provider::IronworksProvider;
+use crate::model::{ItemId, CLASSJOB_NAMES};
+
+use egui::ImageSource;
+use egui_file::FileDialog;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+const SLOT_SIZE: f32 = 32.0;
+
+/// Viewer for a character's hotbars and cross hotbars (HOTBAR.DAT).
+#[derive(Default)]
+pub struct HotbarView {
+    path: Option<PathBuf>,
+    hotbars: Option<HotbarFile>,
+    // 0 is the shared layout, anything else is a ClassJob id.
+    selected_class_job: usize,
+    macros: MacroBooks,
+
+    open_file_dialog: Option<FileDialog>,
+
+    status_message: Option<String>,
+}
+
+/// The macro books that go with a HOTBAR.DAT, so macro slots can show the macro's title and icon.
+#[derive(Default)]
+struct MacroBooks {
+    individual: Vec<Macro>,
+    shared: Vec<Macro>,
+}
+
+impl MacroBooks {
+    // MACRO.DAT sits next to HOTBAR.DAT in the character's folder, and MACROSYS.DAT in the folder
+    // above. Either may be missing, in which case its macros just go unnamed.
+    fn load(hotbar_path: &Path) -> Self {
+        let read = |path: Option<PathBuf>| {
+            let path = path?;
+            read_macro_content(&path)
+                .map_err(|err| log::warn!("unable to read {:?}: {}", path, err))
+                .ok()
+        };
+
+        let character_dir = hotbar_path.parent();
+        Self {
+            individual: read(character_dir.map(|dir| dir.join("MACRO.DAT"))).unwrap_or_default(),
+            shared: read(
+                character_dir
+                    .and_then(Path::parent)
+                    .map(|dir| dir.join("MACROSYS.DAT")),
+            )
+            .unwrap_or_default(),
+        }
+    }
+}
+
+fn resolve_macro<'a>(
+    data_provider: &'a IronworksProvider,
+    macros: &MacroBooks,
+    slot: &HotbarSlot,
+) -> (String, Option<ImageSource<'a>>) {
+    let Some((shared, index)) = slot.macro_index() else {
+        return (String::new(), None);
+    };
+    let (book, label) = match shared {
+        true => (&macros.shared, "Shared macro"),
+        false => (&macros.individual, "Individual macro"),
+    };
+
+    match book.get(index) {
+        Some(m) => (
+            format!("{} {}: {}", label, index, m.title),
+            macro_icon_id(m).and_then(|id| data_provider.get_ui_image_by_id(id).ok()),
+        ),
+        None => (format!("{} {}", label, index), None),
+    }
+}
+
+/// Works out what to call a slot and which icon to draw for it.
+fn resolve_slot<'a>(
+    data_provider: &'a IronworksProvider,
+    macros: &MacroBooks,
+    slot: &HotbarSlot,
+) -> (String, Option<ImageSource<'a>>) {
+    let action = match slot.kind {
+        HotbarSlotKind::Empty => return (String::new(), None),
+        HotbarSlotKind::Action => data_provider.get_action(slot.id),
+        HotbarSlotKind::GeneralAction => data_provider.get_general_action(slot.id),
+        HotbarSlotKind::Emote => data_provider.get_emote(slot.id),
+        HotbarSlotKind::CraftAction => data_provider.get_craft_action(slot.id),
+        HotbarSlotKind::Item => {
+            return match data_provider.get_item(ItemId(slot.id)) {
+                Ok(item) => (item.name, data_provider.get_image(&item.icon).ok()),
+                Err(_) => (format!("Item {}", slot.id), None),
+            };
+        }
+        HotbarSlotKind::Macro => return resolve_macro(data_provider, macros, slot),
+        HotbarSlotKind::Gearset => return (format!("Gearset {}", slot.id + 1), None),
+        kind => return (format!("{:?} {}", kind, slot.id), None),
+    };
+
+    match action {
+        Ok(action) => (action.name, data_provider.get_image(&action.icon).ok()),
+        Err(_) => (format!("{:?} {}", slot.kind, slot.id), None),
+    }
+}
+
+fn show_bar(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    macros: &MacroBooks,
+    label: &str,
+    bar: &[HotbarSlot],
+) {
+    ui.horizontal(|ui| {
+        ui.add_sized([96.0, SLOT_SIZE], egui::Label::new(label));
+
+        for (i, slot) in bar.iter().enumerate() {
+            // Cross hotbars are two sets of eight, one for each trigger.
+            if i == 8 && bar.len() > HOTBAR_VISIBLE_SLOTS {
+                ui.add_space(12.0);
+            }
+
+            let (name, image) = resolve_slot(data_provider, macros, slot);
+            let response = match image {
+                Some(image) => ui.add(
+                    egui::Image::new(image).fit_to_exact_size(egui::vec2(SLOT_SIZE, SLOT_SIZE)),
+                ),
+                None => {
+                    let (rect, response) = ui.allocate_exact_size(
+                        egui::vec2(SLOT_SIZE, SLOT_SIZE),
+                        egui::Sense::hover(),
+                    );
+                    ui.painter().rect_stroke(
+                        rect,
+                        4.0,
+                        ui.visuals().widgets.noninteractive.bg_stroke,
+                        egui::StrokeKind::Inside,
+                    );
+                    response
+                }
+            };
+
+            if !name.is_empty() {
+                response.on_hover_text(name);
+            }
+        }
+    });
+}
+
+fn show_layout(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    macros: &MacroBooks,
+    layout: &HotbarLayout,
+) {
+    ui.heading("Hotbars");
+    for (i, bar) in layout.iter().take(HOTBAR_COUNT).enumerate() {
+        show_bar(
+            ui,
+            data_provider,
+            macros,
+            &format!("Hotbar {}", i + 1),
+            &bar[..HOTBAR_VISIBLE_SLOTS],
+        );
+    }
+
+    ui.separator();
+    ui.heading("Cross hotbars");
+    for (i, bar) in layout.iter().skip(HOTBAR_COUNT).enumerate() {
+        show_bar(
+            ui,
+            data_provider,
+            macros,
+            &format!("Cross hotbar {}", i + 1),
+            bar,
+        );
+    }
+}
+
+fn layout_is_empty(layout: &HotbarLayout) -> bool {
+    layout.iter().flatten().all(HotbarSlot::is_empty)
+}
+
+impl HotbarView {
+    pub fn open(&mut self, path: PathBuf) {
+        match load_hotbars(&path) {
+            Ok(hotbars) => {
+                self.hotbars = Some(hotbars);
+                self.selected_class_job = 0;
+                self.macros = MacroBooks::load(&path);
+                self.status_message = None;
+                self.path = Some(path);
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.status_message = Some(err);
+            }
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        data_provider: &IronworksProvider,
+        start_dir: Option<&Path>,
+    ) {
+        egui::SidePanel::left("hotbar_layouts")
+            .min_width(120.0)
+            .max_width(120.0)
+            .show(ctx, |ui| {
+                let Some(hotbars) = &self.hotbars else {
+                    return;
+                };
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.selectable_value(&mut self.selected_class_job, 0, "Shared");
+                    for (class_job, layout) in hotbars.class_jobs.iter().enumerate().skip(1) {
+                        if layout_is_empty(layout) {
+                            continue;
+                        }
+                        ui.selectable_value(
+                            &mut self.selected_class_job,
+                            class_job,
+                            CLASSJOB_NAMES[class_job],
+                        );
+                    }
+                });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Open…").clicked() {
+                let filter = Box::new({
+                    let ext = Some(OsStr::new("DAT"));
+                    move |path: &Path| -> bool { path.extension() == ext }
+                });
+                let mut dialog = FileDialog::open_file(
+                    self.path
+                        .clone()
+                        .or_else(|| start_dir.map(|dir| dir.join("HOTBAR.DAT"))),
+                )
+                .show_files_filter(filter);
+                dialog.open();
+                self.open_file_dialog = Some(dialog);
+            }
+
+            if let Some(message) = &self.status_message {
+                ui.label(message);
+            }
+            ui.separator();
+
+            let Some(hotbars) = &self.hotbars else {
+                ui.label("Open a HOTBAR.DAT to see its hotbars.");
+                return;
+            };
+
+            let layout = match self.selected_class_job {
+                0 => &hotbars.shared,
+                class_job => &hotbars.class_jobs[class_job],
+            };
+            egui::ScrollArea::both().show(ui, |ui| {
+                show_layout(ui, data_provider, &self.macros, layout);
+            });
+        });
+
+        self.run_load_dialog(ctx);
+    }
+
+    fn run_load_dialog(&mut self, ctx: &egui::Context) {
+        let mut selected_path = None;
+        if let Some(dialog) = &mut self.open_file_dialog {
+            if dialog.show(ctx).selected() {
+                selected_path = dialog.path().map(|path| path.to_path_buf());
+            }
+        }
+
+        if let Some(path) = selected_path {
+            self.open_file_dialog = None;
+            self.open(path);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::iw_provider::IronworksProvider;
+use crate::model::base_param::base_param_display_name;
+use crate::model::{Item, ItemId, PortableItem};
+
+pub const MATERIA_ICON_SIZE: f32 = 20.0;
+
+// The stats an item gives, with its HQ bonuses folded in when it's HQ.
+fn item_stats(item: &Item) -> Vec<(i32, i32)> {
+    let mut stats: Vec<(i32, i32)> = item
+        .base_param_ids
+        .iter()
+        .zip(item.base_param_values.iter())
+        .map(|(param, value)| (*param, *value as i32))
+        .collect();
+
+    if item.id.is_hq() {
+        for (param, value) in item
+            .base_param_special_ids
+            .iter()
+            .zip(item.base_param_special_values.iter())
+        {
+            match stats.iter_mut().find(|(p, _)| p == param) {
+                Some((_, total)) => *total += *value as i32,
+                None => stats.push((*param, *value as i32)),
+            }
+        }
+    }
+
+    stats
+}
+
+/// Shows one meld: the materia's icon, name and stat, or a relic stat bonus.
+pub fn show_materia(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    materia_type: u16,
+    grade: u8,
+) {
+    let Ok(materia) = data_provider.get_materia(materia_type as u32) else {
+        ui.label(format!("Materia {}/{}", materia_type, grade));
+        return;
+    };
+
+    let materia_item = materia
+        .item_id
+        .get(grade as usize)
+        .copied()
+        .filter(|id| *id != 0)
+        .and_then(|id| data_provider.get_item(ItemId(id)).ok());
+    let value = materia
+        .base_param_value
+        .get(grade as usize)
+        .copied()
+        .unwrap_or(0);
+    let stat = format!(
+        "{} +{}",
+        base_param_display_name(materia.base_param_id),
+        value
+    );
+
+    ui.horizontal(|ui| match materia_item {
+        Some(materia_item) => {
+            if let Ok(img) = data_provider.get_image(&materia_item.icon) {
+                ui.add(egui::Image::new(img).max_width(MATERIA_ICON_SIZE))
+                    .widget_info(|| {
+                        egui::WidgetInfo::labeled(egui::WidgetType::Image, true, &materia_item.name)
+                    });
+            }
+            ui.label(format!("{} ({})", materia_item.name, stat));
+        }
+        // Relic stat bonuses are stored like materia, but don't have an item.
+        None => {
+            ui.add_space(MATERIA_ICON_SIZE + ui.spacing().item_spacing.x);
+            ui.label(
+                egui::RichText::new(stat)
+                    .italics()
+                    .color(ui.visuals().hyperlink_color),
+            );
+        }
+    });
+}
+
+/// Shows an item the way the game's item card does, along with what's melded into it.
+///
+/// `id_salt` tells cards apart, since a hover card can be up while the Item window shows another.
+pub fn show_item_card(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    item: &Item,
+    equipped: &PortableItem,
+    id_salt: &str,
+) {
+    ui.horizontal(|ui| {
+        if let Ok(img) = data_provider.get_image(&item.icon) {
+            ui.add(egui::Image::new(img).max_width(40.0))
+                .widget_info(|| {
+                    egui::WidgetInfo::labeled(egui::WidgetType::Image, true, &item.name)
+                });
+        }
+        ui.vertical(|ui| {
+            ui.strong(&item.name);
+            ui.label(format!("Item Level {}", item.level_item));
+        });
+    });
+
+    let class_jobs = data_provider
+        .get_class_job_category_name(item.class_job_category)
+        .unwrap_or_default();
+    ui.label(format!("{}  Lv. {}", class_jobs, item.level_equip));
+
+    ui.separator();
+    if item.damage_phys != 0 || item.damage_mag != 0 {
+        ui.label(format!(
+            "Physical Damage {}  Magic Damage {}  Delay {:.2}",
+            item.damage_phys,
+            item.damage_mag,
+            item.delay_ms as f32 / 1000.0
+        ));
+    }
+    if item.defense_phys != 0 || item.defense_mag != 0 {
+        ui.label(format!(
+            "Defense {}  Magic Defense {}",
+            item.defense_phys, item.defense_mag
+        ));
+    }
+    if item.block != 0 || item.block_rate != 0 {
+        ui.label(format!(
+            "Block Strength {}  Block Rate {}",
+            item.block, item.block_rate
+        ));
+    }
+
+    let stats = item_stats(item);
+    if !stats.is_empty() {
+        egui::Grid::new((id_salt, "item_card_stats")).show(ui, |ui| {
+            for (i, (param, value)) in stats.iter().enumerate() {
+                ui.label(format!("{} +{}", base_param_display_name(*param), value));
+                if i % 2 == 1 {
+                    ui.end_row();
+                }
+            }
+        });
+    }
+
+    let melded = equipped.melds();
+    if item.materia_slot_count != 0 || !melded.is_empty() {
+        ui.separator();
+        ui.label(format!(
+            "Materia ({} slots{})",
+            item.materia_slot_count,
+            if item.advanced_melds_permitted {
+                ", overmeldable"
+            } else {
+                ""
+            }
+        ));
+        for (materia_type, grade) in melded.iter() {
+            show_materia(ui, data_provider, *materia_type, *grade);
+        }
+        for _ in melded.len()..item.materia_slot_count as usize {
+            ui.weak("Empty slot");
+        }
+    }
+
+    let mut flags = Vec::new();
+    if item.dye_count != 0 {
+        flags.push(match item.dye_count {
+            1 => "1 dye channel".to_string(),
+            n => format!("{} dye channels", n),
+        });
+    }
+    if item.is_unique {
+        flags.push("Unique".to_string());
+    }
+    if item.is_untradable {
+        flags.push("Untradable".to_string());
+    }
+    if item.is_crest_worthy {
+        flags.push("Crest-worthy".to_string());
+    }
+    if !flags.is_empty() {
+        ui.separator();
+        ui.weak(flags.join(" · "));
+    }
+
+    if !item.description.is_empty() {
+        ui.separator();
+        ui.label(egui::RichText::new(&item.description).italics());
+    }
+}
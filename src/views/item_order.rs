@@ -0,0 +1,235 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use libxivdat::xiv_gearset::{Gearset, GearsetFlags, GearsetList};
+
+use crate::data_provider::DataProvider;
+use crate::item_order_file::{load_item_order, SortedContainer};
+use crate::iw_provider::IronworksProvider;
+use crate::model::{class_job_abbreviation, EquipSlot, ItemId};
+
+use egui_file::FileDialog;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+// The game lays containers out five slots wide.
+const SLOTS_PER_ROW: usize = 5;
+const ITEM_ICON_SIZE: f32 = 24.0;
+
+/// Viewer for how a character's inventory, armoury chest and retainers are sorted (ITEMODR.DAT).
+#[derive(Default)]
+pub struct ItemOrderView {
+    path: Option<PathBuf>,
+    containers: Vec<SortedContainer>,
+    selected: usize,
+
+    open_file_dialog: Option<FileDialog>,
+
+    status_message: Option<String>,
+}
+
+/// The items gearsets put in the given slots, each with the sets that use it.
+fn gear_in_slots<'a>(
+    gearsets: &'a GearsetList,
+    equip_slots: &[EquipSlot],
+) -> BTreeMap<ItemId, Vec<&'a Gearset>> {
+    let mut users: BTreeMap<ItemId, Vec<&Gearset>> = BTreeMap::new();
+    for gearset in gearsets
+        .gearsets
+        .iter()
+        .filter(|gs| (gs.flags & GearsetFlags::Exists) == GearsetFlags::Exists)
+    {
+        for equip_slot in equip_slots {
+            let Some(eq) = gearset.equipment.get(equip_slot.index()) else {
+                continue;
+            };
+            let item_id = ItemId(eq.item_id);
+            if item_id.is_empty() {
+                continue;
+            }
+
+            let sets = users.entry(item_id).or_default();
+            if !sets.iter().any(|gs| gs.set_number == gearset.set_number) {
+                sets.push(gearset);
+            }
+        }
+    }
+    users
+}
+
+// ITEMODR.DAT holds no item ids, so this can't look inside a chest. It lists the gearset items
+// for the chest's slots instead, which is what's worth keeping if it's in there.
+fn show_gearset_gear(
+    ui: &mut egui::Ui,
+    data_provider: &IronworksProvider,
+    gearsets: Option<&GearsetList>,
+    container: &SortedContainer,
+) {
+    ui.heading("Gearset items for this chest's slots");
+    let Some(gearsets) = gearsets else {
+        ui.label("Open a GEARSET.DAT to see the gearset items for this chest's slots.");
+        return;
+    };
+
+    let users = gear_in_slots(gearsets, container.equip_slots);
+    if users.is_empty() {
+        ui.label("None of your gearsets have an item in this chest's slots.");
+        return;
+    }
+    ui.label(format!(
+        "Your gearsets use these {} items in this chest's slots. ITEMODR.DAT has no item ids, so \
+         whether each one is in this chest, and what else is, is only known in-game.",
+        users.len()
+    ));
+    ui.add_space(4.0);
+
+    egui::Grid::new("item_order_gearset_gear")
+        .striped(true)
+        .show(ui, |ui| {
+            for (item_id, sets) in users.iter() {
+                let item = data_provider.get_item(*item_id).ok();
+                match item
+                    .as_ref()
+                    .and_then(|item| data_provider.get_image(&item.icon).ok())
+                {
+                    Some(img) => {
+                        ui.add(
+                            egui::Image::new(img)
+                                .fit_to_exact_size(egui::vec2(ITEM_ICON_SIZE, ITEM_ICON_SIZE)),
+                        );
+                    }
+                    None => {
+                        ui.label("");
+                    }
+                }
+
+                let name = item
+                    .map(|item| item.name)
+                    .unwrap_or_else(|| format!("Unknown item {}", item_id.base()));
+                ui.label(if item_id.is_hq() {
+                    format!("{} (HQ)", name)
+                } else {
+                    name
+                });
+
+                let sets: Vec<String> = sets
+                    .iter()
+                    .map(|gs| {
+                        format!(
+                            "#{} {} ({})",
+                            gs.set_number,
+                            gs.name,
+                            class_job_abbreviation(gs.class_job)
+                        )
+                    })
+                    .collect();
+                ui.weak(sets.join(", "));
+                ui.end_row();
+            }
+        });
+}
+
+impl ItemOrderView {
+    pub fn open(&mut self, path: PathBuf) {
+        match load_item_order(&path) {
+            Ok(containers) => {
+                self.containers = containers;
+                self.selected = 0;
+                self.status_message = None;
+                self.path = Some(path);
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.status_message = Some(err);
+            }
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        data_provider: &IronworksProvider,
+        gearsets: Option<&GearsetList>,
+        start_dir: Option<&Path>,
+    ) {
+        egui::SidePanel::left("item_order_containers")
+            .min_width(200.0)
+            .max_width(200.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (i, container) in self.containers.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, i, &container.name);
+                    }
+                });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Open…").clicked() {
+                let filter = Box::new({
+                    let ext = Some(OsStr::new("DAT"));
+                    move |path: &Path| -> bool { path.extension() == ext }
+                });
+                let mut dialog = FileDialog::open_file(
+                    self.path
+                        .clone()
+                        .or_else(|| start_dir.map(|dir| dir.join("ITEMODR.DAT"))),
+                )
+                .show_files_filter(filter);
+                dialog.open();
+                self.open_file_dialog = Some(dialog);
+            }
+
+            if let Some(message) = &self.status_message {
+                ui.label(message);
+            }
+            ui.separator();
+
+            let Some(container) = self.containers.get(self.selected) else {
+                ui.label("Open an ITEMODR.DAT to see how your containers are sorted.");
+                return;
+            };
+
+            ui.heading(&container.name);
+            ui.weak(
+                "ITEMODR.DAT only stores the sort order, not item ids, so positions can't show \
+                 item names or icons. Each one shows the container and slot the game draws \
+                 there.",
+            );
+            ui.add_space(8.0);
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("item_order_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (i, slot) in container.slots.iter().enumerate() {
+                            ui.monospace(format!("{}:{}", slot.container, slot.slot));
+                            if (i + 1) % SLOTS_PER_ROW == 0 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+
+                if !container.equip_slots.is_empty() {
+                    ui.separator();
+                    show_gearset_gear(ui, data_provider, gearsets, container);
+                }
+            });
+        });
+
+        self.run_load_dialog(ctx);
+    }
+
+    fn run_load_dialog(&mut self, ctx: &egui::Context) {
+        let mut selected_path = None;
+        if let Some(dialog) = &mut self.open_file_dialog {
+            if dialog.show(ctx).selected() {
+                selected_path = dialog.path().map(|path| path.to_path_buf());
+            }
+        }
+
+        if let Some(path) = selected_path {
+            self.open_file_dialog = None;
+            self.open(path);
+        }
+    }
+}
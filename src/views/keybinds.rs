@@ -0,0 +1,296 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::exporters::{get_keybind_json, ExportTarget};
+use crate::importers::parse_keybind_json;
+use crate::keybind_file::{display_binding, load_keybinds, save_keybinds, Keybind};
+
+use egui_file::FileDialog;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Viewer for a character's keybinds (KEYBIND.DAT), optionally compared against another's.
+#[derive(Default)]
+pub struct KeybindView {
+    path: Option<PathBuf>,
+    keybinds: Vec<Keybind>,
+
+    compare_path: Option<PathBuf>,
+    compare_keybinds: Option<Vec<Keybind>>,
+    only_differences: bool,
+
+    search: String,
+
+    open_file_dialog: Option<FileDialog>,
+    // Whether the open dialog is picking the file to compare against.
+    opening_comparison: bool,
+
+    import_window_open: bool,
+    import_text: String,
+
+    status_message: Option<String>,
+}
+
+// Bindings that differ from ours are highlighted.
+fn show_compared_binding(ui: &mut egui::Ui, ours: &str, theirs: &str) {
+    let text = display_binding(theirs);
+    if ours == theirs {
+        ui.label(text);
+    } else {
+        ui.colored_label(ui.visuals().warn_fg_color, text);
+    }
+}
+
+impl KeybindView {
+    pub fn open(&mut self, path: PathBuf) {
+        match load_keybinds(&path) {
+            Ok(keybinds) => {
+                self.keybinds = keybinds;
+                self.status_message = None;
+                self.path = Some(path);
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.status_message = Some(err);
+            }
+        }
+    }
+
+    fn open_comparison(&mut self, path: PathBuf) {
+        match load_keybinds(&path) {
+            Ok(keybinds) => {
+                self.compare_keybinds = Some(keybinds);
+                self.status_message = None;
+                self.compare_path = Some(path);
+            }
+            Err(err) => {
+                log::error!("{}", err);
+                self.status_message = Some(err);
+            }
+        }
+    }
+
+    fn import(&mut self, json: &str) -> Result<(), String> {
+        let Some(path) = self.path.clone() else {
+            return Err("Open a KEYBIND.DAT first.".to_string());
+        };
+
+        let keybinds = parse_keybind_json(json).map_err(|err| err.to_string())?;
+        let backup_path = save_keybinds(&path, &keybinds)?;
+
+        self.open(path);
+        self.status_message = Some(format!(
+            "Imported keybinds. The original was backed up to {}.",
+            backup_path.display()
+        ));
+        Ok(())
+    }
+
+    fn show_open_dialog(&mut self, start: Option<PathBuf>, comparison: bool) {
+        let filter = Box::new({
+            let ext = Some(OsStr::new("DAT"));
+            move |path: &Path| -> bool { path.extension() == ext }
+        });
+        let mut dialog = FileDialog::open_file(start).show_files_filter(filter);
+        dialog.open();
+        self.open_file_dialog = Some(dialog);
+        self.opening_comparison = comparison;
+    }
+
+    /// Shows the keybinds, returning an export if one was asked for.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        start_dir: Option<&Path>,
+    ) -> Option<(ExportTarget, String)> {
+        let mut export = None;
+
+        self.show_import_window(ctx);
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let start = self
+                    .path
+                    .clone()
+                    .or_else(|| start_dir.map(|dir| dir.join("KEYBIND.DAT")));
+                if ui.button("Open…").clicked() {
+                    self.show_open_dialog(start.clone(), false);
+                }
+                if ui.button("Compare with…").clicked() {
+                    // The other character's folder is a sibling of this one.
+                    self.show_open_dialog(
+                        self.compare_path.clone().or(start.and_then(|path| {
+                            path.parent().and_then(Path::parent).map(Path::to_path_buf)
+                        })),
+                        true,
+                    );
+                }
+
+                ui.add_enabled_ui(!self.keybinds.is_empty(), |ui| {
+                    if ui.button("Export JSON").clicked() {
+                        export =
+                            Some((ExportTarget::KeybindJson, get_keybind_json(&self.keybinds)));
+                    }
+                    if ui.button("Import JSON").clicked() {
+                        self.import_window_open = true;
+                    }
+                });
+            });
+
+            if let Some(message) = &self.status_message {
+                ui.label(message);
+            }
+            ui.separator();
+
+            if self.keybinds.is_empty() {
+                ui.label("Open a KEYBIND.DAT to see its keybinds.");
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                ui.text_edit_singleline(&mut self.search);
+                if let Some(path) = &self.compare_path {
+                    ui.separator();
+                    ui.checkbox(&mut self.only_differences, "Only differences");
+                    ui.weak(format!("Comparing with {}", path.display()));
+                }
+            });
+
+            self.show_table(ui);
+        });
+
+        self.run_load_dialog(ctx);
+
+        export
+    }
+
+    fn show_table(&self, ui: &mut egui::Ui) {
+        use egui_extras::{Column, TableBuilder};
+
+        let search = self.search.to_lowercase();
+        let rows: Vec<(&Keybind, Option<&Keybind>)> = self
+            .keybinds
+            .iter()
+            .filter(|keybind| keybind.command.to_lowercase().contains(&search))
+            .map(|keybind| {
+                let other = self
+                    .compare_keybinds
+                    .as_ref()
+                    .and_then(|other| other.iter().find(|k| k.command == keybind.command));
+                (keybind, other)
+            })
+            .filter(|(keybind, other)| {
+                !self.only_differences || other.is_some_and(|other| other != *keybind)
+            })
+            .collect();
+
+        let comparing = self.compare_keybinds.is_some();
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto());
+        if comparing {
+            table = table.column(Column::auto()).column(Column::remainder());
+        }
+
+        table
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Command");
+                });
+                header.col(|ui| {
+                    ui.strong("Primary");
+                });
+                header.col(|ui| {
+                    ui.strong("Secondary");
+                });
+                if comparing {
+                    header.col(|ui| {
+                        ui.strong("Other primary");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Other secondary");
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(18.0, rows.len(), |mut row| {
+                    let (keybind, other) = rows[row.index()];
+                    row.col(|ui| {
+                        ui.label(&keybind.command);
+                    });
+                    row.col(|ui| {
+                        ui.label(display_binding(&keybind.primary));
+                    });
+                    row.col(|ui| {
+                        ui.label(display_binding(&keybind.secondary));
+                    });
+                    if comparing {
+                        row.col(|ui| {
+                            if let Some(other) = other {
+                                show_compared_binding(ui, &keybind.primary, &other.primary);
+                            }
+                        });
+                        row.col(|ui| {
+                            if let Some(other) = other {
+                                show_compared_binding(ui, &keybind.secondary, &other.secondary);
+                            }
+                        });
+                    }
+                });
+            });
+    }
+
+    fn show_import_window(&mut self, ctx: &egui::Context) {
+        if !self.import_window_open {
+            return;
+        }
+
+        let mut import_clicked = false;
+        egui::Window::new("Import keybinds")
+            .open(&mut self.import_window_open)
+            .resizable([true, true])
+            .show(ctx, |ui| {
+                ui.label(
+                    "Paste keybinds exported from roarich. They'll replace the bindings for the \
+                     same commands in the open KEYBIND.DAT.",
+                );
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.import_text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+                import_clicked = ui.button("Import").clicked();
+            });
+
+        if import_clicked {
+            let json = std::mem::take(&mut self.import_text);
+            match self.import(&json) {
+                Ok(()) => self.import_window_open = false,
+                Err(err) => {
+                    self.import_text = json;
+                    self.status_message = Some(err);
+                }
+            }
+        }
+    }
+
+    fn run_load_dialog(&mut self, ctx: &egui::Context) {
+        let mut selected_path = None;
+        if let Some(dialog) = &mut self.open_file_dialog {
+            if dialog.show(ctx).selected() {
+                selected_path = dialog.path().map(|path| path.to_path_buf());
+            }
+        }
+
+        if let Some(path) = selected_path {
+            self.open_file_dialog = None;
+            match self.opening_comparison {
+                true => self.open_comparison(path),
+                false => self.open(path),
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use libxivdat::dat_file::write_content;
+use libxivdat::xiv_macro::{read_macro_content, to_writeable_bytes, Macro};
+
+use crate::data_provider::DataProvider;
+use crate::exporters::{get_macro_json, get_macro_text, macro_is_empty, ExportTarget};
+use crate::gearset_file::backup_file;
+use crate::importers::parse_macro_json;
+use crate::iw_provider::IronworksProvider;
+
+use egui_file::FileDialog;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+// icon_id is the UI icon's id, as hex. (icon_key is the row in the MacroIcon sheet, which isn't
+// an icon id at all.)
+pub fn macro_icon_id(m: &Macro) -> Option<u32> {
+    u32::from_str_radix(&m.icon_id, 16)
+        .ok()
+        .filter(|id| *id != 0)
+}
+
+/// Viewer for individual (MACRO.DAT) and shared (MACROSYS.DAT) macro books.
+#[derive(Default)]
+pub struct MacroView {
+    path: Option<PathBuf>,
+    macros: Vec<Macro>,
+    selected: usize,
+
+    open_file_dialog: Option<FileDialog>,
+
+    import_window_open: bool,
+    import_text: String,
+
+    status_message: Option<String>,
+}
+
+impl MacroView {
+    pub fn open(&mut self, path: PathBuf) {
+        match read_macro_content(&path) {
+            Ok(macros) => {
+                self.macros = macros;
+                self.selected = 0;
+                self.status_message = None;
+                self.path = Some(path);
+            }
+            Err(err) => {
+                log::error!("unable to read {:?}: {}", path, err);
+                self.status_message = Some(format!("Unable to read {}: {}", path.display(), err));
+            }
+        }
+    }
+
+    /// Replaces macros in the open book, backing up the file before writing it.
+    fn import(&mut self, json: &str) -> Result<(), String> {
+        let Some(path) = self.path.clone() else {
+            return Err("Open a macro book first.".to_string());
+        };
+
+        let imported = parse_macro_json(json).map_err(|err| err.to_string())?;
+
+        let mut macros = self.macros.clone();
+        for (index, m) in imported {
+            if index >= macros.len() {
+                return Err(format!("Macro {} doesn't fit in this book.", index));
+            }
+            macros[index] = m;
+        }
+
+        let bytes = to_writeable_bytes(&macros).map_err(|err| err.to_string())?;
+        let backup_path = backup_file(&path)
+            .map_err(|err| format!("Unable to back up {}: {}", path.display(), err))?;
+        write_content(&path, &bytes)
+            .map_err(|err| format!("Unable to write {}: {}", path.display(), err))?;
+
+        self.macros = macros;
+        self.status_message = Some(format!(
+            "Imported macros. The original was backed up to {}.",
+            backup_path.display()
+        ));
+        Ok(())
+    }
+
+    /// Shows the macro book, returning an export if one was asked for.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        data_provider: &IronworksProvider,
+        start_dir: Option<&Path>,
+    ) -> Option<(ExportTarget, String)> {
+        let mut export = None;
+
+        self.show_import_window(ctx);
+
+        egui::SidePanel::left("macro_list")
+            .min_width(200.0)
+            .max_width(200.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (i, m) in self.macros.iter().enumerate() {
+                        if macro_is_empty(m) {
+                            continue;
+                        }
+
+                        ui.horizontal(|ui| {
+                            if let Some(img) = macro_icon_id(m)
+                                .and_then(|id| data_provider.get_ui_image_by_id(id).ok())
+                            {
+                                ui.add(egui::Image::new(img).max_width(16.0));
+                            }
+                            if ui
+                                .selectable_label(self.selected == i, format!("{} {}", i, m.title))
+                                .clicked()
+                            {
+                                self.selected = i;
+                            }
+                        });
+                    }
+                });
+            });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Open…").clicked() {
+                    let filter = Box::new({
+                        let ext = Some(OsStr::new("DAT"));
+                        move |path: &Path| -> bool { path.extension() == ext }
+                    });
+                    let mut dialog = FileDialog::open_file(
+                        self.path
+                            .clone()
+                            .or_else(|| start_dir.map(Path::to_path_buf)),
+                    )
+                    .show_files_filter(filter);
+                    dialog.open();
+                    self.open_file_dialog = Some(dialog);
+                }
+
+                ui.add_enabled_ui(!self.macros.is_empty(), |ui| {
+                    if ui.button("Export text").clicked() {
+                        export = Some((ExportTarget::MacroText, get_macro_text(&self.macros)));
+                    }
+                    if ui.button("Export JSON").clicked() {
+                        export = Some((ExportTarget::MacroJson, get_macro_json(&self.macros)));
+                    }
+                    if ui.button("Import JSON").clicked() {
+                        self.import_window_open = true;
+                    }
+                });
+            });
+
+            if let Some(message) = &self.status_message {
+                ui.label(message);
+            }
+            ui.separator();
+
+            match &self.path {
+                Some(path) => {
+                    ui.weak(path.display().to_string());
+                }
+                None => {
+                    ui.label(
+                        "Open a MACRO.DAT (individual macros) or MACROSYS.DAT (shared macros).",
+                    );
+                }
+            }
+
+            if let Some(m) = self.macros.get(self.selected) {
+                ui.horizontal(|ui| {
+                    if let Some(img) =
+                        macro_icon_id(m).and_then(|id| data_provider.get_ui_image_by_id(id).ok())
+                    {
+                        ui.add(egui::Image::new(img).max_width(32.0));
+                    }
+                    ui.heading(format!("{}: {}", self.selected, m.title));
+                });
+
+                for line in m.lines.iter() {
+                    ui.monospace(line);
+                }
+            }
+        });
+
+        self.run_load_dialog(ctx);
+
+        export
+    }
+
+    fn show_import_window(&mut self, ctx: &egui::Context) {
+        if !self.import_window_open {
+            return;
+        }
+
+        let mut import_clicked = false;
+        egui::Window::new("Import macros")
+            .open(&mut self.import_window_open)
+            .resizable([true, true])
+            .show(ctx, |ui| {
+                ui.label(
+                    "Paste macros exported from roarich. They'll replace the macros in the same \
+                     slots of the open book.",
+                );
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.import_text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+                import_clicked = ui.button("Import").clicked();
+            });
+
+        if import_clicked {
+            let json = std::mem::take(&mut self.import_text);
+            match self.import(&json) {
+                Ok(()) => self.import_window_open = false,
+                Err(err) => {
+                    self.import_text = json;
+                    self.status_message = Some(err);
+                }
+            }
+        }
+    }
+
+    fn run_load_dialog(&mut self, ctx: &egui::Context) {
+        let mut selected_path = None;
+        if let Some(dialog) = &mut self.open_file_dialog {
+            if dialog.show(ctx).selected() {
+                selected_path = dialog.path().map(|path| path.to_path_buf());
+            }
+        }
+
+        if let Some(path) = selected_path {
+            self.open_file_dialog = None;
+            self.open(path);
+        }
+    }
+}
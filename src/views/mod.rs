@@ -0,0 +1,17 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+mod glamour;
+mod hotbars;
+mod item_card;
+mod item_order;
+mod keybinds;
+mod macros;
+mod paper_doll;
+
+pub use glamour::show_glamour;
+pub use hotbars::HotbarView;
+pub use item_card::{show_item_card, show_materia, MATERIA_ICON_SIZE};
+pub use item_order::ItemOrderView;
+pub use keybinds::KeybindView;
+pub use macros::MacroView;
+pub use paper_doll::PaperDoll;
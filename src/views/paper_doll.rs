@@ -0,0 +1,164 @@
+#![warn(clippy::all, rust_2018_idioms)]
+
+use crate::data_provider::DataProvider;
+use crate::iw_provider::IronworksProvider;
+use crate::model::{EquipSlot, ItemId};
+
+use image::{imageops, Rgba, RgbaImage};
+use libxivdat::xiv_gearset::Gearset;
+
+const CELL_SIZE: u32 = 64;
+const CELL_PADDING: u32 = 4;
+const SWATCH_SIZE: u32 = 12;
+// The framed job icons, same as the gearset heading.
+const CLASS_JOB_FRAMED_BASE_ID: u32 = 62100;
+// Halfway down the middle column, above the soul crystal.
+const JOB_CELL: (u32, u32) = (1, 2);
+
+// Where each slot goes, as (column, row), laid out like the in-game character window: armour on
+// the left, accessories on the right, the job in the middle.
+fn slot_cell(slot: EquipSlot) -> Option<(u32, u32)> {
+    match slot {
+        EquipSlot::MainHand => Some((0, 0)),
+        EquipSlot::Head => Some((0, 1)),
+        EquipSlot::Body => Some((0, 2)),
+        EquipSlot::Hands => Some((0, 3)),
+        EquipSlot::Legs => Some((0, 4)),
+        EquipSlot::Feet => Some((0, 5)),
+        EquipSlot::OffHand => Some((2, 0)),
+        EquipSlot::Ears => Some((2, 1)),
+        EquipSlot::Neck => Some((2, 2)),
+        EquipSlot::Wrists => Some((2, 3)),
+        EquipSlot::LeftRing => Some((2, 4)),
+        EquipSlot::RightRing => Some((2, 5)),
+        EquipSlot::SoulCrystal => Some((1, 5)),
+        EquipSlot::Waist => None,
+    }
+}
+
+fn cell_origin((column, row): (u32, u32)) -> (i64, i64) {
+    (
+        (column * (CELL_SIZE + CELL_PADDING) + CELL_PADDING) as i64,
+        (row * (CELL_SIZE + CELL_PADDING) + CELL_PADDING) as i64,
+    )
+}
+
+fn fill_rect(canvas: &mut RgbaImage, x: i64, y: i64, width: u32, height: u32, color: Rgba<u8>) {
+    imageops::overlay(canvas, &RgbaImage::from_pixel(width, height, color), x, y);
+}
+
+/// Composites a gearset's icons into a single picture, showing glamours (and their dyes) over
+/// the equipped items so it looks the way the set is worn.
+///
+/// This draws each item's icon rather than its model. Putting the models together would need a
+/// model loader and a rasteriser, and the icons are enough to plan a glamour by.
+pub fn render_paper_doll(gearset: &Gearset, data_provider: &IronworksProvider) -> RgbaImage {
+    let width = 3 * (CELL_SIZE + CELL_PADDING) + CELL_PADDING;
+    let height = 6 * (CELL_SIZE + CELL_PADDING) + CELL_PADDING;
+    let mut canvas = RgbaImage::new(width, height);
+
+    let empty_cell = Rgba([255, 255, 255, 24]);
+    for (slot, eq) in EquipSlot::ALL.iter().zip(gearset.equipment.iter()) {
+        let Some(cell) = slot_cell(*slot) else {
+            continue;
+        };
+        let (x, y) = cell_origin(cell);
+        fill_rect(&mut canvas, x, y, CELL_SIZE, CELL_SIZE, empty_cell);
+
+        let shown_id = match eq.glamour_id {
+            0 => ItemId(eq.item_id),
+            glamour_id => ItemId(glamour_id),
+        };
+        if shown_id.is_empty() {
+            continue;
+        }
+
+        let Some(icon) = data_provider
+            .get_item(shown_id)
+            .ok()
+            .and_then(|item| data_provider.get_texture(&item.icon).ok())
+        else {
+            continue;
+        };
+        let icon = imageops::resize(&icon, CELL_SIZE, CELL_SIZE, imageops::FilterType::Triangle);
+        imageops::overlay(&mut canvas, &icon, x, y);
+
+        // A swatch per dye channel along the bottom of the cell.
+        for (i, stain_id) in eq.stains.iter().enumerate().filter(|(_, s)| **s != 0) {
+            let Ok(stain) = data_provider.get_stain(*stain_id as u32) else {
+                continue;
+            };
+            let [_, r, g, b] = stain.color.to_be_bytes();
+            fill_rect(
+                &mut canvas,
+                x + (CELL_SIZE - (i as u32 + 1) * SWATCH_SIZE) as i64,
+                y + (CELL_SIZE - SWATCH_SIZE) as i64,
+                SWATCH_SIZE,
+                SWATCH_SIZE,
+                Rgba([r, g, b, 255]),
+            );
+        }
+    }
+
+    // The job in the middle, where the character would be. It gets a cell of its own so it can't
+    // spill over into the armour and accessory columns on either side.
+    if let Ok(job_icon) =
+        data_provider.get_ui_texture_by_id(CLASS_JOB_FRAMED_BASE_ID + gearset.class_job as u32)
+    {
+        let job_icon = imageops::resize(
+            &job_icon,
+            CELL_SIZE,
+            CELL_SIZE,
+            imageops::FilterType::Triangle,
+        );
+        let (x, y) = cell_origin(JOB_CELL);
+        imageops::overlay(&mut canvas, &job_icon, x, y);
+    }
+
+    canvas
+}
+
+/// Shows the paper doll for a gearset, only re-rendering it when the set changes.
+#[derive(Default)]
+pub struct PaperDoll {
+    // What the texture was rendered from: the job, then each slot's item, glamour and dyes.
+    key: Vec<u32>,
+    texture: Option<egui::TextureHandle>,
+}
+
+impl PaperDoll {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        data_provider: &IronworksProvider,
+        gearset: &Gearset,
+    ) {
+        let mut key = vec![gearset.class_job as u32];
+        for eq in gearset.equipment.iter() {
+            key.push(eq.item_id);
+            key.push(eq.glamour_id);
+            key.extend(eq.stains.iter().map(|s| *s as u32));
+        }
+
+        if self.texture.is_none() || self.key != key {
+            let image = render_paper_doll(gearset, data_provider);
+            let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                [image.width() as usize, image.height() as usize],
+                image.as_raw(),
+            );
+            self.texture = Some(ui.ctx().load_texture(
+                "paper_doll",
+                color_image,
+                Default::default(),
+            ));
+            self.key = key;
+        }
+
+        if let Some(texture) = &self.texture {
+            let label = format!("Preview of {}", gearset.name);
+            ui.image(texture)
+                .on_hover_text("Item icons, with glamours and dyes applied")
+                .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Image, true, &label));
+        }
+    }
+}